@@ -10,22 +10,7 @@ use std::io::Write;
 #[derive(Serialize, Deserialize, Debug)]
 struct OutputData {
     note: String,
-    states: Vec<Vec<Vec<String>>>,
-}
-
-// each column contains a row
-fn grid_to_vec(g: &Grid) -> Vec<Vec<String>> {
-    let mut data = vec![];
-    for v in 0..g.get_vertical_size() {
-        let mut row: Vec<String> = vec![];
-        for h in 0..g.get_horizontal_size() {
-            let cs = g.get_cellstate(h, v);
-            let csstr = format!("{:?}", cs); // convert CellState to string
-            row.push(csstr);
-        }
-        data.push(row);
-    }
-    data
+    states: Vec<Grid>,
 }
 
 // implementation of rule 30
@@ -62,18 +47,18 @@ fn main() {
     // test with center cell alive
     let mut u = Universe::new(3, 1, rule30);
     u.grid.set_cellstate(1, 0, CellState::Alive);
-    odata.states.push(grid_to_vec(&u.grid));
+    odata.states.push(u.grid);
 
     // all cells become alive in first iteration (apply the rule)
     u.update();
-    odata.states.push(grid_to_vec(&u.grid));
+    odata.states.push(u.grid);
 
     // another update and all die (and stay dead)
     u.update();
-    odata.states.push(grid_to_vec(&u.grid));
+    odata.states.push(u.grid);
 
     u.update();
-    odata.states.push(grid_to_vec(&u.grid));
+    odata.states.push(u.grid);
 
     // serialize data into JSON string
     let serialized = serde_json::to_string(&odata);
@@ -2,21 +2,6 @@
 // https://mathworld.wolfram.com/Rule30.html
 use lysogeny_broth::*;
 
-// just print the grid on the terminal
-fn print_grid(g: &Grid) {
-    for v in 0..g.get_vertical_size() {
-        for h in 0..g.get_horizontal_size() {
-            let cs = g.get_cellstate(h, v);
-            if cs == &CellState::Alive {
-                print!("o");
-            } else {
-                print!("x");
-            }
-        }
-        println!();
-    }
-}
-
 fn main() {
     println!("Wolfram rule 30 example");
 
@@ -45,16 +30,16 @@ fn main() {
     // test with center cell alive
     let mut u = Universe::new(3, 1, rule30);
     u.grid.set_cellstate(1, 0, CellState::Alive);
-    print_grid(&u.grid);
+    print!("{}", u.grid.display_with('o', 'x'));
 
     // all cells become alive in first iteration (apply the rule)
     u.update();
-    print_grid(&u.grid);
+    print!("{}", u.grid.display_with('o', 'x'));
 
     // another update and all die (and stay dead)
     u.update();
-    print_grid(&u.grid);
+    print!("{}", u.grid.display_with('o', 'x'));
 
     u.update();
-    print_grid(&u.grid);
+    print!("{}", u.grid.display_with('o', 'x'));
 }
@@ -0,0 +1,61 @@
+// WireWorld example: electrons passing a diode
+// https://en.wikipedia.org/wiki/Wireworld
+use lysogeny_broth::rules::wireworld;
+use lysogeny_broth::*;
+
+// print the grid, one line per row
+fn print_grid(g: &Grid) {
+    for v in 0..g.get_vertical_size() {
+        let row: String = (0..g.get_horizontal_size())
+            .map(|h| match g.get_cellstate(h, v) {
+                CellState::Empty => ' ',
+                CellState::ElectronHead => 'H',
+                CellState::ElectronTail => 't',
+                CellState::Conductor => '#',
+            })
+            .collect();
+        println!("{}", row);
+    }
+}
+
+// diode: electrons pass from left to right only
+//      ##
+// ##### ####
+//      ##
+fn diode() -> Universe {
+    let mut u = Universe::new(12, 5, wireworld);
+    for h in (1..11).filter(|h| *h != 6) {
+        u.grid.set_cellstate(h, 2, CellState::Conductor);
+    }
+    for hv in [(5, 1), (6, 1), (5, 3), (6, 3)] {
+        u.grid.set_cellstate_hv(hv, CellState::Conductor);
+    }
+    u
+}
+
+// run a universe and print every generation
+fn run(u: &mut Universe) {
+    for generation in 0..10 {
+        println!("generation {}", generation);
+        print_grid(&u.grid);
+        u.update();
+    }
+}
+
+fn main() {
+    println!("WireWorld example");
+
+    // an electron on the left passes the diode ...
+    println!("left to right");
+    let mut u = diode();
+    u.grid.set_cellstate(1, 2, CellState::ElectronTail);
+    u.grid.set_cellstate(2, 2, CellState::ElectronHead);
+    run(&mut u);
+
+    // ... one on the right is blocked
+    println!("right to left");
+    let mut u = diode();
+    u.grid.set_cellstate(10, 2, CellState::ElectronTail);
+    u.grid.set_cellstate(9, 2, CellState::ElectronHead);
+    run(&mut u);
+}
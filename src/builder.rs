@@ -0,0 +1,172 @@
+//! Step by step configuration of a universe.
+use crate::{CellState, Grid, Topology, Universe};
+
+/// Keep every cell unchanged (the rule until one is given).
+///
+/// # Arguments
+/// * `h`: horizontal coordinate
+/// * `v`: vertical coordinate
+/// * `g`: the current grid
+fn unchanged(h: u8, v: u8, g: &Grid) -> CellState {
+    *g.get_cellstate(h, v)
+}
+
+/// Configure a universe with chained method calls, e.g.
+/// `UniverseBuilder::new().dimensions(32, 32).rule(r).build()`.
+/// `Universe::new()` covers the simple cases.
+#[derive(Copy, Clone, Debug)]
+pub struct UniverseBuilder {
+    /// Horizontal and vertical size.
+    dimensions: (u8, u8),
+    /// The rules of the universe.
+    rule: fn(u8, u8, &Grid) -> CellState,
+    /// Shape of the grid.
+    topology: Topology,
+    /// Seed and probability of living cells for random noise.
+    #[cfg(feature = "dead-alive-only")]
+    random: Option<(u64, u8)>,
+}
+
+impl UniverseBuilder {
+    /// Start a configuration: no dimensions yet, cells stay
+    /// unchanged, a toroidal grid and all cells dead.
+    pub fn new() -> UniverseBuilder {
+        UniverseBuilder {
+            dimensions: (0, 0),
+            rule: unchanged,
+            topology: Topology::Toroidal,
+            #[cfg(feature = "dead-alive-only")]
+            random: None,
+        }
+    }
+
+    /// Set the size of the grid.
+    ///
+    /// # Arguments
+    /// * `h_size`: horizontal dimension/size as number of cells
+    /// * `v_size`: vertical dimension/size as number of cells
+    pub fn dimensions(mut self, h_size: u8, v_size: u8) -> UniverseBuilder {
+        self.dimensions = (h_size, v_size);
+        self
+    }
+
+    /// Set the rules of the universe.
+    ///
+    /// # Arguments
+    /// * `rule`: a function mapping a coordinate (and thus the state of a cell) on a grid to a new state
+    pub fn rule(mut self, rule: fn(u8, u8, &Grid) -> CellState) -> UniverseBuilder {
+        self.rule = rule;
+        self
+    }
+
+    /// Set the shape of the grid.
+    ///
+    /// # Arguments
+    /// * `topology`: behaviour at all edges
+    pub fn topology(mut self, topology: Topology) -> UniverseBuilder {
+        self.topology = topology;
+        self
+    }
+
+    /// Fill the grid with random noise (see `Grid::randomize()`).
+    ///
+    /// # Arguments
+    /// * `seed`: seed of the pseudo random number generator
+    /// * `alive_probability`: chance of a living cell in 256th
+    #[cfg(feature = "dead-alive-only")]
+    pub fn seed_random(mut self, seed: u64, alive_probability: u8) -> UniverseBuilder {
+        self.random = Some((seed, alive_probability));
+        self
+    }
+
+    /// Create the configured universe.
+    ///
+    /// # Panics
+    /// If no or invalid dimensions were given.
+    pub fn build(self) -> Universe {
+        let (h_size, v_size) = self.dimensions;
+        let mut universe = Universe::new(h_size, v_size, self.rule);
+        let grid = Grid::with_topology(h_size, v_size, self.topology);
+        #[cfg(feature = "dead-alive-only")]
+        let grid = self.random.map_or(grid, |(seed, alive_probability)| {
+            let mut seeded = grid;
+            seeded.randomize(seed, alive_probability);
+            seeded
+        });
+        // the seeded grid is the starting point of `reset()`
+        universe.reset_to(grid);
+        universe
+    }
+}
+
+impl Default for UniverseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "dead-alive-only")]
+    use crate::test_rules::conway;
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn builder_conway() {
+        let mut u = UniverseBuilder::new()
+            .dimensions(20, 10)
+            .rule(conway)
+            .seed_random(9, 100)
+            .build();
+        let mut seeded = Grid::new(20, 10);
+        seeded.randomize(9, 100);
+        assert!(u.grid == seeded);
+
+        // same result as a universe built by hand
+        let mut expected = Universe::new(20, 10, conway);
+        expected.grid = seeded;
+        u.update();
+        expected.update();
+        assert!(u.grid == expected.grid);
+        assert_eq!(u.generation(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn builder_reset() {
+        let mut u = UniverseBuilder::new()
+            .dimensions(20, 10)
+            .seed_random(9, 100)
+            .build();
+        let seeded = u.grid;
+        assert!(seeded.population() > 0);
+        // before any update
+        u.reset();
+        assert!(u.grid == seeded);
+
+        u.update();
+        u.reset();
+        assert!(u.grid == seeded);
+        assert_eq!(u.generation(), 0);
+    }
+
+    #[test]
+    fn builder_defaults() {
+        let mut u = UniverseBuilder::new()
+            .dimensions(4, 3)
+            .topology(Topology::Bounded)
+            .build();
+        let before = u.grid;
+        u.update();
+        assert!(u.grid == before);
+        assert_eq!(u.grid.get_horizontal_size(), 4);
+        assert_eq!(u.grid.neighbour(0, 0, crate::Direction::North), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn builder_no_dimensions() {
+        let _ = UniverseBuilder::new().build();
+    }
+}
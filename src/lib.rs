@@ -46,12 +46,99 @@ limitations under the License.
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "alloc")]
+use alloc::{collections::VecDeque, vec::Vec};
+
 /// Tweak here for vertical grid size / memory usage.
 /// `u8` was chosen to accommodate memory constraints.
 const VERTICAL_MAX: usize = u8::MAX as usize;
 /// Tweak here for horizontal grid size / memory usage.
 /// `u8` was chosen to accommodate memory constraints.
 const HORIZONTAL_MAX: usize = u8::MAX as usize;
+/// Number of bytes needed to hold one bit per cell of a row.
+#[cfg(any(feature = "alloc", feature = "dead-alive-only"))]
+const HORIZONTAL_BYTES: usize = HORIZONTAL_MAX.div_ceil(8);
+
+/// Number of 64 bit words needed to hold one bit per cell of a row.
+#[cfg(feature = "packed")]
+const HORIZONTAL_WORDS: usize = HORIZONTAL_MAX.div_ceil(64);
+
+/// Storage of the cell states of a grid, one `CellState` per cell.
+#[cfg(not(feature = "packed"))]
+type CellArray = [[CellState; HORIZONTAL_MAX]; VERTICAL_MAX];
+/// Storage of the cell states of a grid, one bit per cell (set = alive).
+/// Rows are indexed vertically, the first cell of a byte is its
+/// most significant bit (see `cs8_into_u8()`).
+#[cfg(feature = "packed")]
+type CellArray = [[u8; HORIZONTAL_BYTES]; VERTICAL_MAX];
+
+/// Version of the byte format written by `Grid::serialize_to()`.
+#[cfg(feature = "dead-alive-only")]
+const PACKED_FORMAT_VERSION: u8 = 1;
+
+/// Number of generations kept by `Universe::population_history()`.
+#[cfg(feature = "history")]
+pub const HISTORY_CAPACITY: usize = 64;
+
+/// Offset basis of the 64 bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// Prime of the 64 bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Feed bytes into a 64 bit FNV-1a hash.
+///
+/// # Arguments
+/// * `hash`: the current hash value (start with `FNV_OFFSET_BASIS`)
+/// * `bytes`: the data to add
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Advance a xorshift64* pseudo random number generator
+/// and return the next number.
+///
+/// # Arguments
+/// * `state`: the state of the generator (must not be 0)
+#[cfg(feature = "dead-alive-only")]
+fn xorshift64star(state: &mut u64) -> u64 {
+    *state ^= *state >> 12;
+    *state ^= *state << 25;
+    *state ^= *state >> 27;
+    state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+}
+
+#[cfg(all(feature = "multistate", feature = "dead-alive-only"))]
+compile_error!("feature \"multistate\" can not be combined with \"dead-alive-only\" (disable the default features)");
+#[cfg(all(feature = "wireworld", feature = "dead-alive-only"))]
+compile_error!("feature \"wireworld\" can not be combined with \"dead-alive-only\" (disable the default features)");
+#[cfg(all(feature = "wireworld", feature = "multistate"))]
+compile_error!("feature \"wireworld\" can not be combined with \"multistate\"");
+
+mod builder;
+#[cfg(feature = "life106")]
+mod life106;
+mod line;
+#[cfg(feature = "plaintext")]
+mod plaintext;
+#[cfg(feature = "rle")]
+mod rle;
+pub mod rules;
+#[cfg(feature = "serde")]
+mod serialization;
+mod static_grid;
+#[cfg(all(test, feature = "dead-alive-only"))]
+mod test_rules;
+pub use builder::UniverseBuilder;
+pub use line::Line;
+pub use static_grid::StaticGrid;
 
 /// The state of a cell.
 ///
@@ -59,8 +146,14 @@ const HORIZONTAL_MAX: usize = u8::MAX as usize;
 /// A cell has no concept of its neighbours. Everything
 /// in terms of space is handled by the Grid.
 #[derive(Copy, Clone, Debug, PartialEq)]
-#[cfg(not(feature = "dead-alive-only"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg(not(any(
+    feature = "dead-alive-only",
+    feature = "multistate",
+    feature = "wireworld"
+)))]
 pub enum CellState {
+    /// placeholder state without any meaning
     Dummy,
 }
 
@@ -71,6 +164,7 @@ pub enum CellState {
 /// A cell has no concept of its neighbours. Everything
 /// in terms of space is handled by the Grid.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg(feature = "dead-alive-only")]
 pub enum CellState {
     /// represents a dead cell
@@ -79,7 +173,75 @@ pub enum CellState {
     Alive,
 }
 
+/// The state of a cell as a number, for automata with
+/// more than two states (e.g. Brian's Brain or WireWorld).
+/// The whole `u8` range is valid, `0` is the default
+/// (quiescent) state. The meaning of all other values
+/// is up to the rules.
+///
+/// # Remarks
+/// A cell has no concept of its neighbours. Everything
+/// in terms of space is handled by the Grid.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg(feature = "multistate")]
+pub struct CellState(pub u8);
+
+/// The state of a cell in WireWorld.
+///
+/// # Remarks
+/// A cell has no concept of its neighbours. Everything
+/// in terms of space is handled by the Grid.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg(feature = "wireworld")]
+pub enum CellState {
+    /// represents an empty cell (never changes)
+    Empty,
+    /// represents the head of an electron
+    ElectronHead,
+    /// represents the tail of an electron
+    ElectronTail,
+    /// represents a conductor (i.e. wire)
+    Conductor,
+}
+
 impl CellState {
+    /// Encode the state as a byte (e.g. for hashing or serialization).
+    fn to_byte(self) -> u8 {
+        #[cfg(feature = "multistate")]
+        return self.0;
+        #[cfg(not(feature = "multistate"))]
+        return self as u8;
+    }
+
+    /// Decode a state from a byte written by `to_byte()`.
+    ///
+    /// # Arguments
+    /// * `byte`: the encoded state
+    fn from_byte(byte: u8) -> Option<CellState> {
+        #[cfg(feature = "multistate")]
+        return Some(CellState(byte));
+        #[cfg(not(feature = "multistate"))]
+        return match byte {
+            #[cfg(not(any(feature = "dead-alive-only", feature = "wireworld")))]
+            0 => Some(CellState::Dummy),
+            #[cfg(feature = "dead-alive-only")]
+            0 => Some(CellState::Dead),
+            #[cfg(feature = "dead-alive-only")]
+            1 => Some(CellState::Alive),
+            #[cfg(feature = "wireworld")]
+            0 => Some(CellState::Empty),
+            #[cfg(feature = "wireworld")]
+            1 => Some(CellState::ElectronHead),
+            #[cfg(feature = "wireworld")]
+            2 => Some(CellState::ElectronTail),
+            #[cfg(feature = "wireworld")]
+            3 => Some(CellState::Conductor),
+            _ => None,
+        };
+    }
+
     #[cfg(feature = "dead-alive-into-bool")]
     /// If cells can be either alive or dead, then
     /// their state could be converted into a boolean
@@ -93,9 +255,29 @@ impl CellState {
     }
 }
 
+impl Default for CellState {
+    /// The state of a freshly created cell (i.e. a dead one).
+    fn default() -> Self {
+        #[cfg(not(any(
+            feature = "dead-alive-only",
+            feature = "multistate",
+            feature = "wireworld"
+        )))]
+        return CellState::Dummy;
+        #[cfg(feature = "dead-alive-only")]
+        return CellState::Dead;
+        #[cfg(feature = "multistate")]
+        return CellState(0);
+        #[cfg(feature = "wireworld")]
+        return CellState::Empty;
+    }
+}
+
 #[cfg(feature = "dead-alive-u8-utils")]
 /// Convert eight binary cell states into a u8 / octet.
 /// A dead cell becomes a 0, an alive one a 1.
+/// The first cell ends up in the most significant bit
+/// (see `cs8_into_u8_lsb()` for the opposite order).
 pub fn cs8_into_u8(cs: [&CellState; 8]) -> u8 {
     let mut rdata: u8 = 0b00000000;
     for s in cs.iter() {
@@ -103,37 +285,256 @@ pub fn cs8_into_u8(cs: [&CellState; 8]) -> u8 {
         if s == &&CellState::Alive {
             // set bit
             let bit: u8 = 0b00000001;
-            rdata = rdata | bit;
+            rdata |= bit;
         }
     }
-    return rdata;
+    rdata
 }
 
 #[cfg(feature = "dead-alive-u8-utils")]
 /// Convert eight binary cell states into a u8 / octet.
 /// A dead cell becomes a 0, an alive one a 1.
-pub fn u8_into_cs8(bits: u8) -> [&'static CellState; 8] {
+/// The first cell ends up in the least significant bit
+/// (see `cs8_into_u8()` for the opposite order).
+pub fn cs8_into_u8_lsb(cs: [&CellState; 8]) -> u8 {
+    let mut rdata: u8 = 0b00000000;
+    for (i, s) in cs.iter().enumerate() {
+        if s == &&CellState::Alive {
+            rdata |= 1 << i;
+        }
+    }
+    rdata
+}
+
+#[cfg(feature = "dead-alive-u8-utils")]
+/// Convert a u8 / octet into eight binary cell states,
+/// the inverse of `cs8_into_u8()`. A 0 becomes a dead cell,
+/// a 1 an alive one. The most significant bit ends up first.
+pub fn u8_into_cs8(bits: u8) -> [CellState; 8] {
     let mut mask: u8 = 0b00000001;
-    let mut rdata = [
-        &CellState::Dead,
-        &CellState::Dead,
-        &CellState::Dead,
-        &CellState::Dead,
-        &CellState::Dead,
-        &CellState::Dead,
-        &CellState::Dead,
-        &CellState::Dead,
-    ];
-    for i in 0..8 {
+    let mut rdata = [CellState::Dead; 8];
+    for cs in rdata.iter_mut() {
         mask = mask.rotate_right(1);
         let bit = mask & bits;
         if bit == 0 {
-            rdata[i] = &CellState::Dead;
+            *cs = CellState::Dead;
         } else {
-            rdata[i] = &CellState::Alive;
+            *cs = CellState::Alive;
+        }
+    }
+    rdata
+}
+
+/// A position on the grid.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Coordinate {
+    /// horizontal coordinate
+    pub h: u8,
+    /// vertical coordinate
+    pub v: u8,
+}
+
+impl From<(u8, u8)> for Coordinate {
+    /// Convert a tuple (horizontal coordinate, vertical coordinate).
+    fn from(hv: (u8, u8)) -> Self {
+        Coordinate { h: hv.0, v: hv.1 }
+    }
+}
+
+impl From<Coordinate> for (u8, u8) {
+    /// Convert into a tuple (horizontal coordinate, vertical coordinate).
+    fn from(c: Coordinate) -> Self {
+        (c.h, c.v)
+    }
+}
+
+/// Errors when storing or restoring a grid as packed bytes.
+#[cfg(feature = "dead-alive-only")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PackError {
+    /// The data was written in an unknown format version.
+    UnsupportedVersion(u8),
+    /// The data ends before all cells were read.
+    Truncated,
+    /// The stored grid dimensions are invalid.
+    InvalidDimensions,
+    /// The buffer can not hold the packed grid.
+    BufferTooSmall,
+}
+
+#[cfg(feature = "dead-alive-only")]
+impl core::fmt::Display for PackError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PackError::UnsupportedVersion(version) => {
+                write!(f, "unsupported packed format version {}", version)
+            }
+            PackError::Truncated => write!(f, "packed data truncated"),
+            PackError::InvalidDimensions => write!(f, "invalid grid dimensions"),
+            PackError::BufferTooSmall => write!(f, "buffer too small"),
+        }
+    }
+}
+
+/// Symmetry operations on a grid.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Symmetry {
+    /// mirroring horizontally, i.e. swapping left and right
+    Horizontal,
+    /// mirroring vertically, i.e. swapping top and bottom
+    Vertical,
+    /// rotating by 180 degrees
+    Rotation180,
+}
+
+/// Behaviour of a grid axis at its edges.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BoundaryMode {
+    /// the axis wraps around, i.e. the last cell neighbours the first
+    Wrap,
+    /// the axis ends at the edge, there are no cells beyond it
+    Fixed,
+}
+
+/// Directions of the neighbouring cells (Moore neighbourhood).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Direction {
+    /// one cell up
+    North,
+    /// one cell up and to the right
+    NorthEast,
+    /// one cell to the right
+    East,
+    /// one cell down and to the right
+    SouthEast,
+    /// one cell down
+    South,
+    /// one cell down and to the left
+    SouthWest,
+    /// one cell to the left
+    West,
+    /// one cell up and to the left
+    NorthWest,
+}
+
+impl Direction {
+    /// All directions, clockwise starting in the north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+}
+
+/// Travel direction of a moving pattern (e.g. a glider).
+#[cfg(feature = "conway")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Orientation {
+    /// moving up and to the right
+    NorthEast,
+    /// moving down and to the right
+    SouthEast,
+    /// moving down and to the left
+    SouthWest,
+    /// moving up and to the left
+    NorthWest,
+}
+
+/// Shape of the whole grid, i.e. the behaviour at all edges.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Topology {
+    /// both axes wrap around (torus)
+    Toroidal,
+    /// there are no cells beyond the edges
+    Bounded,
+}
+
+/// Errors when accessing a grid.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GridError {
+    /// The horizontal coordinate is equal to or larger than the horizontal size.
+    HorizontalOutOfBounds,
+    /// The vertical coordinate is equal to or larger than the vertical size.
+    VerticalOutOfBounds,
+    /// A grid size is zero or too large.
+    InvalidDimensions,
+    /// The operation needs a square grid (e.g. rotation).
+    NotSquare,
+    /// The rows of the input differ in length.
+    NotRectangular,
+}
+
+impl core::fmt::Display for GridError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            GridError::HorizontalOutOfBounds => write!(f, "horizontal coordinate too large"),
+            GridError::VerticalOutOfBounds => write!(f, "vertical coordinate too large"),
+            GridError::InvalidDimensions => write!(f, "invalid grid dimensions"),
+            GridError::NotSquare => write!(f, "grid is not square"),
+            GridError::NotRectangular => write!(f, "rows differ in length"),
+        }
+    }
+}
+
+/// Errors when reading a pattern.
+#[cfg(any(feature = "rle", feature = "plaintext", feature = "life106"))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PatternError {
+    /// The header line (e.g. `x = .., y = ..` of RLE) is malformed.
+    InvalidHeader,
+    /// A character is not part of the format.
+    InvalidTag(char),
+    /// A run count is too large.
+    InvalidCount,
+    /// The pattern does not fit into the grid.
+    TooLarge,
+    /// A coordinate is malformed or incomplete.
+    InvalidCoordinate,
+}
+
+#[cfg(any(feature = "rle", feature = "plaintext", feature = "life106"))]
+impl core::fmt::Display for PatternError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PatternError::InvalidHeader => write!(f, "invalid header"),
+            PatternError::InvalidTag(c) => write!(f, "invalid tag {:?}", c),
+            PatternError::InvalidCount => write!(f, "invalid run count"),
+            PatternError::TooLarge => write!(f, "pattern larger than grid"),
+            PatternError::InvalidCoordinate => write!(f, "invalid coordinate"),
+        }
+    }
+}
+
+/// Errors when reading cell data.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// The data ends in the middle of an entry.
+    Truncated,
+    /// A coordinate lies outside of the grid.
+    OutOfBounds,
+    /// A byte does not encode a valid cell state.
+    InvalidState(u8),
+    /// The grid dimensions are invalid.
+    InvalidDimensions,
+    /// The rows differ in length.
+    NotRectangular,
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::Truncated => write!(f, "data truncated"),
+            ParseError::OutOfBounds => write!(f, "coordinate outside of grid"),
+            ParseError::InvalidState(byte) => write!(f, "invalid cell state {}", byte),
+            ParseError::InvalidDimensions => write!(f, "invalid grid dimensions"),
+            ParseError::NotRectangular => write!(f, "rows differ in length"),
         }
     }
-    return rdata;
 }
 
 /// A structure to encode a grid with cells.
@@ -156,15 +557,24 @@ pub struct Grid {
     /// If you need for more adjust the data types as needed.
     vertical_size: u8,
     /// The actual arrays to hold cell states.
-    cells: [[CellState; HORIZONTAL_MAX]; VERTICAL_MAX],
+    cells: CellArray,
     /// The vertical position of the cell iterator.
     horizontal_cell_iterator_index: usize,
     /// The horizontal position of the cell iterator.
     vertical_cell_iterator_index: usize,
     /// The vertical position of the cell iterator for byte export.
+    #[cfg_attr(not(feature = "dead-alive-only"), allow(dead_code))]
     horizontal_byte_iterator_index: usize,
     /// The horizontal position of the cell iterator for byte export.
+    #[cfg_attr(not(feature = "dead-alive-only"), allow(dead_code))]
     vertical_byte_iterator_index: usize,
+    /// Cells excluded from updates, one bit per cell (set = inactive).
+    #[cfg(feature = "alloc")]
+    inactive: [[u8; HORIZONTAL_BYTES]; VERTICAL_MAX],
+    /// Behaviour at the left and right edges.
+    horizontal_boundary: BoundaryMode,
+    /// Behaviour at the top and bottom edges.
+    vertical_boundary: BoundaryMode,
 }
 
 impl Grid {
@@ -182,31 +592,90 @@ impl Grid {
     /// Larger grid sizes have to keep the target usize (thus architecture)
     /// in mind and can be adjusted appropriately.
     pub fn new(h_size: u8, v_size: u8) -> Grid {
-        if h_size == 0 {
-            panic!("horizontal coordinate too small")
-        }
-        if v_size == 0 {
-            panic!("vertical coordinate too small")
-        }
-        if h_size as usize > HORIZONTAL_MAX {
-            panic!("horizontal coordinate too large")
+        match Grid::try_new(h_size, v_size) {
+            Ok(g) => g,
+            Err(e) => panic!("{}", e),
         }
-        if v_size as usize > VERTICAL_MAX {
-            panic!("vertical coordinate too large")
+    }
+
+    /// Create a new grid like `new()`, but return an error
+    /// instead of panicking on invalid dimensions.
+    ///
+    /// # Arguments
+    /// * `h_size`: horizontal dimension/size as number of cells
+    /// * `v_size`: vertical dimension/size as number of cells
+    pub fn try_new(h_size: u8, v_size: u8) -> Result<Grid, GridError> {
+        if h_size == 0
+            || v_size == 0
+            || h_size as usize > HORIZONTAL_MAX
+            || v_size as usize > VERTICAL_MAX
+        {
+            return Err(GridError::InvalidDimensions);
         }
 
-        Grid {
+        Ok(Grid {
             horizontal_size: h_size,
             vertical_size: v_size,
             horizontal_cell_iterator_index: 0,
             vertical_cell_iterator_index: 0,
             horizontal_byte_iterator_index: 0,
             vertical_byte_iterator_index: 0,
-            #[cfg(not(feature = "dead-alive-only"))]
-            cells: [[CellState::Dummy; HORIZONTAL_MAX]; VERTICAL_MAX],
-            #[cfg(feature = "dead-alive-only")]
-            cells: [[CellState::Dead; HORIZONTAL_MAX]; VERTICAL_MAX],
-        }
+            #[cfg(feature = "alloc")]
+            inactive: [[0; HORIZONTAL_BYTES]; VERTICAL_MAX],
+            horizontal_boundary: BoundaryMode::Wrap,
+            vertical_boundary: BoundaryMode::Wrap,
+            #[cfg(not(feature = "packed"))]
+            cells: [[CellState::default(); HORIZONTAL_MAX]; VERTICAL_MAX],
+            #[cfg(feature = "packed")]
+            cells: [[0; HORIZONTAL_BYTES]; VERTICAL_MAX],
+        })
+    }
+
+    /// Create a new grid with separate boundary behaviour per
+    /// axis, e.g. a cylinder wrapping horizontally but not
+    /// vertically. `Grid::new()` wraps both axes (torus).
+    ///
+    /// # Arguments
+    /// * `h_size`: horizontal dimension/size as number of cells
+    /// * `v_size`: vertical dimension/size as number of cells
+    /// * `horizontal`: behaviour at the left and right edges
+    /// * `vertical`: behaviour at the top and bottom edges
+    pub fn with_axis_boundaries(
+        h_size: u8,
+        v_size: u8,
+        horizontal: BoundaryMode,
+        vertical: BoundaryMode,
+    ) -> Grid {
+        let mut g = Grid::new(h_size, v_size);
+        g.horizontal_boundary = horizontal;
+        g.vertical_boundary = vertical;
+        g
+    }
+
+    /// Create a new grid with the given topology. Under
+    /// `Topology::Bounded` neighbours beyond the edges do
+    /// not exist (see `neighbour()`) and count as dead.
+    ///
+    /// # Arguments
+    /// * `h_size`: horizontal dimension/size as number of cells
+    /// * `v_size`: vertical dimension/size as number of cells
+    /// * `topology`: the shape of the grid
+    pub fn with_topology(h_size: u8, v_size: u8, topology: Topology) -> Grid {
+        let mode = match topology {
+            Topology::Toroidal => BoundaryMode::Wrap,
+            Topology::Bounded => BoundaryMode::Fixed,
+        };
+        Grid::with_axis_boundaries(h_size, v_size, mode, mode)
+    }
+
+    /// Get the behaviour at the left and right edges.
+    pub fn get_horizontal_boundary(&self) -> BoundaryMode {
+        self.horizontal_boundary
+    }
+
+    /// Get the behaviour at the top and bottom edges.
+    pub fn get_vertical_boundary(&self) -> BoundaryMode {
+        self.vertical_boundary
     }
 
     /// Get the number of columns (i.e. horizontal size)
@@ -225,13 +694,43 @@ impl Grid {
     /// * `h`: horizontal coordinate
     /// * `v`: vertical coordinate
     pub fn get_cellstate(&self, h: u8, v: u8) -> &CellState {
+        match self.try_get_cellstate(h, v) {
+            Ok(state) => state,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Retrieve a cell state without panicking.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    pub fn try_get_cellstate(&self, h: u8, v: u8) -> Result<&CellState, GridError> {
+        self.check_coordinate(h, v)?;
+        #[cfg(not(feature = "packed"))]
+        return Ok(&self.cells[h as usize][v as usize]);
+        #[cfg(feature = "packed")]
+        return Ok(
+            match u8_into_cs8(self.cells[v as usize][h as usize / 8])[h as usize % 8] {
+                CellState::Dead => &CellState::Dead,
+                CellState::Alive => &CellState::Alive,
+            },
+        );
+    }
+
+    /// Check if a coordinate lies on the grid.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    fn check_coordinate(&self, h: u8, v: u8) -> Result<(), GridError> {
         if h >= self.horizontal_size {
-            panic!("horizontal coordinate too large")
+            return Err(GridError::HorizontalOutOfBounds);
         }
         if v >= self.vertical_size {
-            panic!("vertical coordinate too large")
+            return Err(GridError::VerticalOutOfBounds);
         }
-        &self.cells[h as usize][v as usize]
+        Ok(())
     }
 
     /// Retrieve a cell state (for modification) using a coordinate tuple.
@@ -248,13 +747,31 @@ impl Grid {
     /// * `h`: horizontal coordinate
     /// * `v`: vertical coordinate
     pub fn set_cellstate(&mut self, h: u8, v: u8, state: CellState) {
-        if h >= self.horizontal_size {
-            panic!("horizontal coordinate too large")
+        if let Err(e) = self.try_set_cellstate(h, v, state) {
+            panic!("{}", e)
         }
-        if v >= self.vertical_size {
-            panic!("vertical coordinate too large")
+    }
+
+    /// Set a cell state without panicking.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    /// * `state`: the new state of the cell
+    pub fn try_set_cellstate(&mut self, h: u8, v: u8, state: CellState) -> Result<(), GridError> {
+        self.check_coordinate(h, v)?;
+        #[cfg(not(feature = "packed"))]
+        {
+            self.cells[h as usize][v as usize] = state;
         }
-        self.cells[h as usize][v as usize] = state;
+        #[cfg(feature = "packed")]
+        {
+            let byte = &mut self.cells[v as usize][h as usize / 8];
+            let mut states = u8_into_cs8(*byte);
+            states[h as usize % 8] = state;
+            *byte = cs8_into_u8(states.each_ref());
+        }
+        Ok(())
     }
 
     /// Set a (modified) cell state using a coordination tuple.
@@ -265,23 +782,180 @@ impl Grid {
         self.set_cellstate(hv.0, hv.1, state)
     }
 
-    /// Get coordinates of "northern" cell relative
-    /// to the given grid coordinates.
+    /// Retrieve the state of the cell at the given coordinate.
+    ///
+    /// # Arguments
+    /// * `c`: coordinate of the cell
+    ///
+    /// # Panics
+    /// If `c.h` is equal to or larger than the horizontal size
+    /// or `c.v` is equal to or larger than the vertical size.
+    pub fn get(&self, c: Coordinate) -> &CellState {
+        self.get_cellstate(c.h, c.v)
+    }
+
+    /// Set the state of the cell at the given coordinate.
+    ///
+    /// # Arguments
+    /// * `c`: coordinate of the cell
+    /// * `state`: the new state of the cell
+    ///
+    /// # Panics
+    /// If `c.h` is equal to or larger than the horizontal size
+    /// or `c.v` is equal to or larger than the vertical size.
+    pub fn set(&mut self, c: Coordinate, state: CellState) {
+        self.set_cellstate(c.h, c.v, state)
+    }
+
+    /// Reset every cell to the default (dead) state.
+    pub fn clear(&mut self) {
+        self.fill(CellState::default());
+    }
+
+    /// Set every cell to the given state.
+    ///
+    /// # Arguments
+    /// * `state`: the new state of all cells
+    pub fn fill(&mut self, state: CellState) {
+        for h in 0..self.horizontal_size {
+            for v in 0..self.vertical_size {
+                self.set_cellstate(h, v, state);
+            }
+        }
+    }
+
+    /// Iterate over all cells row by row (starting at the top left),
+    /// yielding the horizontal and vertical coordinate and the state.
+    pub fn iter_cells(&self) -> impl Iterator<Item = (u8, u8, &CellState)> + '_ {
+        (0..self.vertical_size).flat_map(move |v| {
+            (0..self.horizontal_size).map(move |h| (h, v, self.get_cellstate(h, v)))
+        })
+    }
+
+    /// Iterate over the coordinates of all cells in the given
+    /// state row by row (starting at the top left).
+    ///
+    /// # Arguments
+    /// * `state`: the state of the cells to find
+    pub fn find_cells(&self, state: CellState) -> impl Iterator<Item = (u8, u8)> + '_ {
+        self.iter_cells()
+            .filter(move |(_, _, cs)| **cs == state)
+            .map(|(h, v, _)| (h, v))
+    }
+
+    /// Iterate over the coordinates of all living cells
+    /// row by row (starting at the top left).
+    #[cfg(feature = "dead-alive-only")]
+    pub fn iter_alive(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        self.find_cells(CellState::Alive)
+    }
+
+    /// Get the smallest rectangle holding all living cells as top
+    /// left and bottom right coordinate (both inclusive), `None` if
+    /// there are no living cells.
+    ///
+    /// # Remarks
+    /// Wrapping edges are not taken into account, e.g. a pattern
+    /// crossing the right edge of a toroidal grid spans the whole width.
+    #[cfg(feature = "dead-alive-only")]
+    pub fn live_bounding_box(&self) -> Option<((u8, u8), (u8, u8))> {
+        self.iter_alive().fold(None, |bounds, (h, v)| match bounds {
+            None => Some(((h, v), (h, v))),
+            Some(((left, top), (right, bottom))) => {
+                Some(((left.min(h), top.min(v)), (right.max(h), bottom.max(v))))
+            }
+        })
+    }
+
+    /// Move a position along an axis by `delta` cells.
+    ///
+    /// # Arguments
+    /// * `position`: the coordinate on the axis
+    /// * `delta`: number of cells to move (-1, 0 or 1)
+    /// * `size`: the size of the axis
+    /// * `mode`: the behaviour at the edges of the axis
+    fn shift(position: u8, delta: i16, size: u8, mode: BoundaryMode) -> Option<u8> {
+        let target = position as i16 + delta;
+        match mode {
+            BoundaryMode::Wrap => Some(target.rem_euclid(size as i16) as u8),
+            BoundaryMode::Fixed => {
+                if (0..size as i16).contains(&target) {
+                    Some(target as u8)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Get the cells within `radius` of a position along an axis as
+    /// first position (possibly negative) and number of cells. Each
+    /// cell is covered at most once, even if the range is wider than
+    /// a wrapping axis.
+    ///
+    /// # Arguments
+    /// * `position`: the coordinate on the axis
+    /// * `radius`: maximal distance from the position
+    /// * `size`: the size of the axis
+    /// * `mode`: the behaviour at the edges of the axis
+    #[cfg(feature = "dead-alive-only")]
+    fn span(position: u8, radius: u8, size: u8, mode: BoundaryMode) -> (i16, i16) {
+        let first = position as i16 - radius as i16;
+        let last = position as i16 + radius as i16;
+        match mode {
+            BoundaryMode::Wrap if last - first >= size as i16 => (0, size as i16),
+            BoundaryMode::Wrap => (first, last - first + 1),
+            BoundaryMode::Fixed => {
+                let first = first.max(0);
+                (first, last.min(size as i16 - 1) - first + 1)
+            }
+        }
+    }
+
+    /// Get coordinates of the neighbouring cell in the given
+    /// direction, respecting the boundary behaviour of each axis.
+    /// Returns `None` if the neighbour lies beyond a fixed boundary.
     ///
     /// # Arguments
     /// * `h`: horizontal coordinate
     /// * `v`: vertical coordinate
-    pub fn get_north_coordinate(&self, h: u8, v: u8) -> (u8, u8) {
+    /// * `direction`: direction of the neighbour
+    pub fn neighbour(&self, h: u8, v: u8, direction: Direction) -> Option<(u8, u8)> {
         if h >= self.horizontal_size {
             panic!("horizontal coordinate too large")
         }
         if v >= self.vertical_size {
             panic!("vertical coordinate too large")
         }
-        if v == 0 {
-            return (h, self.vertical_size - 1);
+        let (dh, dv) = match direction {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        };
+        let h = Grid::shift(h, dh, self.horizontal_size, self.horizontal_boundary)?;
+        let v = Grid::shift(v, dv, self.vertical_size, self.vertical_boundary)?;
+        Some((h, v))
+    }
+
+    /// Get coordinates of "northern" cell relative
+    /// to the given grid coordinates.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    ///
+    /// # Panics
+    /// If the neighbour lies beyond a fixed boundary.
+    pub fn get_north_coordinate(&self, h: u8, v: u8) -> (u8, u8) {
+        match self.neighbour(h, v, Direction::North) {
+            Some(hv) => hv,
+            None => panic!("no northern cell beyond a fixed boundary"),
         }
-        (h, v - 1)
     }
 
     /// Get coordinates of "northern" cell relative
@@ -299,17 +973,14 @@ impl Grid {
     /// # Arguments
     /// * `h`: horizontal coordinate
     /// * `v`: vertical coordinate
+    ///
+    /// # Panics
+    /// If the neighbour lies beyond a fixed boundary.
     pub fn get_east_coordinate(&self, h: u8, v: u8) -> (u8, u8) {
-        if h >= self.horizontal_size {
-            panic!("horizontal coordinate too large")
-        }
-        if v >= self.vertical_size {
-            panic!("vertical coordinate too large")
-        }
-        if h == self.horizontal_size - 1 {
-            return (0, v);
+        match self.neighbour(h, v, Direction::East) {
+            Some(hv) => hv,
+            None => panic!("no eastern cell beyond a fixed boundary"),
         }
-        (h + 1, v)
     }
 
     /// Get coordinates of "eastern" cell relative
@@ -327,17 +998,14 @@ impl Grid {
     /// # Arguments
     /// * `h`: horizontal coordinate
     /// * `v`: vertical coordinate
+    ///
+    /// # Panics
+    /// If the neighbour lies beyond a fixed boundary.
     pub fn get_south_coordinate(&self, h: u8, v: u8) -> (u8, u8) {
-        if h >= self.horizontal_size {
-            panic!("horizontal coordinate too large")
-        }
-        if v >= self.vertical_size {
-            panic!("vertical coordinate too large")
+        match self.neighbour(h, v, Direction::South) {
+            Some(hv) => hv,
+            None => panic!("no southern cell beyond a fixed boundary"),
         }
-        if v == self.vertical_size - 1 {
-            return (h, 0);
-        }
-        (h, v + 1)
     }
 
     /// Get coordinates of "eastern" cell relative
@@ -355,17 +1023,14 @@ impl Grid {
     /// # Arguments
     /// * `h`: horizontal coordinate
     /// * `v`: vertical coordinate
+    ///
+    /// # Panics
+    /// If the neighbour lies beyond a fixed boundary.
     pub fn get_west_coordinate(&self, h: u8, v: u8) -> (u8, u8) {
-        if h >= self.horizontal_size {
-            panic!("horizontal coordinate too large")
+        match self.neighbour(h, v, Direction::West) {
+            Some(hv) => hv,
+            None => panic!("no western cell beyond a fixed boundary"),
         }
-        if v >= self.vertical_size {
-            panic!("vertical coordinate too large")
-        }
-        if h == 0 {
-            return (self.horizontal_size - 1, v);
-        }
-        (h - 1, v)
     }
 
     /// Get coordinates of "western" cell relative
@@ -453,395 +1118,4104 @@ impl Grid {
         self.get_north_coordinate_hv(self.get_west_coordinate(hv.0, hv.1))
     }
 
-    /// Go over the grid row by row and return eight
-    /// cell states as a byte (dead = 0, alive = 1).
-    /// This is an iterator-like utility function.
-    #[cfg(feature = "dead-alive-only")]
-    pub fn next_byte(&mut self) -> Option<u8> {
-        //TODO: check if cell count is a multiple of 8 -> compilation error otherwise
-        let mut rbyte = 0x00u8; // byte to return
-
-        for offset in 0..8 as u8 {
-            if self.horizontal_byte_iterator_index >= self.horizontal_size as usize {
-                // we stepped over the end of a row so ...
-                // ... we go to the next row ...
-                self.vertical_byte_iterator_index += 1;
-                // ... and reset the index pointer
-                self.horizontal_byte_iterator_index = 0;
+    /// Restrict updates to the cells selected by the mask. Cells
+    /// outside of the mask are reset to the default (dead) state
+    /// and keep it, since `Universe::update()` skips them.
+    ///
+    /// # Arguments
+    /// * `mask`: one entry per cell, row by row, `true` marks an active cell
+    ///
+    /// # Remarks
+    /// The mask moves along with the cells when the grid is flipped,
+    /// rotated or rolled.
+    #[cfg(feature = "alloc")]
+    pub fn set_active_mask(&mut self, mask: &[bool]) {
+        if mask.len() != self.horizontal_size as usize * self.vertical_size as usize {
+            panic!("mask size does not match grid size")
+        }
+        for v in 0..self.vertical_size {
+            for h in 0..self.horizontal_size {
+                let bit = 0x80u8 >> (h % 8);
+                let byte = &mut self.inactive[v as usize][h as usize / 8];
+                if mask[v as usize * self.horizontal_size as usize + h as usize] {
+                    *byte &= !bit;
+                } else {
+                    *byte |= bit;
+                    self.set_cellstate(h, v, CellState::default());
+                }
             }
-            if self.vertical_byte_iterator_index >= self.vertical_size as usize {
-                return None;
+        }
+    }
+
+    /// Check if a cell takes part in updates (see `set_active_mask()`).
+    /// Without the `alloc` feature every cell is active.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    pub fn is_active(&self, h: u8, v: u8) -> bool {
+        if h >= self.horizontal_size {
+            panic!("horizontal coordinate too large")
+        }
+        if v >= self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        #[cfg(feature = "alloc")]
+        return self.inactive[v as usize][h as usize / 8] & (0x80u8 >> (h % 8)) == 0;
+        #[cfg(not(feature = "alloc"))]
+        true
+    }
+
+    /// Move the active mask along with the cells of a transformation.
+    ///
+    /// # Arguments
+    /// * `target`: maps the coordinate of a cell to its new coordinate
+    #[cfg(feature = "alloc")]
+    fn remap_active_mask(&mut self, target: impl Fn(u8, u8) -> (u8, u8)) {
+        let source = self.inactive;
+        self.inactive = [[0; HORIZONTAL_BYTES]; VERTICAL_MAX];
+        for v in 0..self.vertical_size {
+            for h in 0..self.horizontal_size {
+                if source[v as usize][h as usize / 8] & (0x80u8 >> (h % 8)) != 0 {
+                    let (new_h, new_v) = target(h, v);
+                    self.inactive[new_v as usize][new_h as usize / 8] |= 0x80u8 >> (new_h % 8);
+                }
             }
+        }
+    }
 
-            match self.get_cellstate(
-                self.horizontal_byte_iterator_index as u8,
-                self.vertical_byte_iterator_index as u8,
-            ) {
-                CellState::Alive => {
-                    // set bit according to offset
-                    rbyte = rbyte | (0x80u8 >> offset);
+    /// Calculate the horizontal and vertical distance between two
+    /// cells, taking the shorter way around the edges on each axis
+    /// (so a distance is at most half the size of the axis).
+    ///
+    /// # Arguments
+    /// * `a`: tuple (horizontal coordinate, vertical coordinate)
+    /// * `b`: tuple (horizontal coordinate, vertical coordinate)
+    ///
+    /// # Remarks
+    /// The edges are always wrapped around, regardless of the boundaries.
+    pub fn toroidal_distance(&self, a: (u8, u8), b: (u8, u8)) -> (u8, u8) {
+        for hv in [a, b] {
+            if hv.0 >= self.horizontal_size {
+                panic!("horizontal coordinate too large")
+            }
+            if hv.1 >= self.vertical_size {
+                panic!("vertical coordinate too large")
+            }
+        }
+        let dh = a.0.abs_diff(b.0);
+        let dv = a.1.abs_diff(b.1);
+        (
+            dh.min(self.horizontal_size - dh),
+            dv.min(self.vertical_size - dv),
+        )
+    }
+
+    /// Calculate the Manhattan (von Neumann) distance between two
+    /// cells, taking the shorter way around the edges on each axis.
+    ///
+    /// # Arguments
+    /// * `from`: tuple (horizontal coordinate, vertical coordinate)
+    /// * `to`: tuple (horizontal coordinate, vertical coordinate)
+    pub fn toroidal_manhattan_distance(&self, from: (u8, u8), to: (u8, u8)) -> u16 {
+        let (dh, dv) = self.toroidal_distance(from, to);
+        dh as u16 + dv as u16
+    }
+
+    /// Get the coordinates of the eight cells of the Moore
+    /// neighbourhood, clockwise starting in the north:
+    /// north, north east, east, south east, south,
+    /// south west, west, north west.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    ///
+    /// # Panics
+    /// If a neighbour lies beyond a fixed boundary.
+    pub fn get_moore_neighbourhood(&self, h: u8, v: u8) -> [(u8, u8); 8] {
+        [
+            self.get_north_coordinate(h, v),
+            self.get_northeast_coordinate(h, v),
+            self.get_east_coordinate(h, v),
+            self.get_southeast_coordinate(h, v),
+            self.get_south_coordinate(h, v),
+            self.get_southwest_coordinate(h, v),
+            self.get_west_coordinate(h, v),
+            self.get_northwest_coordinate(h, v),
+        ]
+    }
+
+    /// Get the coordinates of the six neighbours of a cell on a
+    /// hexagonal grid, clockwise starting in the north east:
+    /// north east, east, south east, south west, west, north west.
+    ///
+    /// The hexagons are stored in rows ("odd-r" offset layout):
+    /// odd rows are shifted half a cell to the right. So the diagonal
+    /// neighbours of a cell in an even row are the cells with the same
+    /// and the next smaller horizontal coordinate, in an odd row the
+    /// ones with the same and the next larger horizontal coordinate.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    ///
+    /// # Panics
+    /// If a neighbour lies beyond a fixed boundary.
+    ///
+    /// # Remarks
+    /// Wrapping vertically only keeps the layout consistent if
+    /// the vertical size is even.
+    pub fn get_hex_neighbourhood(&self, h: u8, v: u8) -> [(u8, u8); 6] {
+        let directions = if v % 2 == 0 {
+            [
+                Direction::North,
+                Direction::East,
+                Direction::South,
+                Direction::SouthWest,
+                Direction::West,
+                Direction::NorthWest,
+            ]
+        } else {
+            [
+                Direction::NorthEast,
+                Direction::East,
+                Direction::SouthEast,
+                Direction::South,
+                Direction::West,
+                Direction::North,
+            ]
+        };
+        directions.map(|direction| match self.neighbour(h, v, direction) {
+            Some(hv) => hv,
+            None => panic!("no hexagonal neighbour beyond a fixed boundary"),
+        })
+    }
+
+    /// Get the states of the eight cells of the Moore neighbourhood
+    /// in the order of `get_moore_neighbourhood()` (clockwise starting
+    /// in the north). Cells beyond a fixed boundary are reported in
+    /// the default state.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    pub fn get_moore_states(&self, h: u8, v: u8) -> [CellState; 8] {
+        self.moore_coordinates(h, v).map(|hv| match hv {
+            Some(hv) => *self.get_cellstate_hv(hv),
+            None => CellState::default(),
+        })
+    }
+
+    /// Get the states of the four cells of the von Neumann
+    /// neighbourhood: north, east, south, west. Cells beyond
+    /// a fixed boundary are reported in the default state.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    pub fn get_von_neumann_states(&self, h: u8, v: u8) -> [CellState; 4] {
+        [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .map(|direction| match self.neighbour(h, v, direction) {
+            Some(hv) => *self.get_cellstate_hv(hv),
+            None => CellState::default(),
+        })
+    }
+
+    /// Coordinates of the eight cells of the Moore neighbourhood
+    /// (N, NE, E, SE, S, SW, W, NW), `None` beyond a fixed boundary.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    fn moore_coordinates(&self, h: u8, v: u8) -> [Option<(u8, u8)>; 8] {
+        Direction::ALL.map(|direction| self.neighbour(h, v, direction))
+    }
+
+    /// Check if at least one cell of the Moore neighbourhood is alive.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    #[cfg(feature = "dead-alive-only")]
+    pub fn any_moore_alive(&self, h: u8, v: u8) -> bool {
+        self.moore_coordinates(h, v)
+            .iter()
+            .flatten()
+            .any(|hv| self.get_cellstate_hv(*hv) == &CellState::Alive)
+    }
+
+    /// Check if all eight cells of the Moore neighbourhood are alive.
+    /// This is never the case next to a fixed boundary.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    #[cfg(feature = "dead-alive-only")]
+    pub fn all_moore_alive(&self, h: u8, v: u8) -> bool {
+        self.moore_coordinates(h, v)
+            .iter()
+            .all(|hv| matches!(hv, Some(hv) if self.get_cellstate_hv(*hv) == &CellState::Alive))
+    }
+
+    /// Count the living cells of the Moore neighbourhood,
+    /// excluding the cell itself (0 to 8).
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    ///
+    /// # Remarks
+    /// Each of the eight neighbour positions is counted on its own,
+    /// even if several of them wrap around to the same cell (e.g.
+    /// on a 1x1 grid the cell is its own neighbour eight times).
+    #[cfg(feature = "dead-alive-only")]
+    pub fn count_live_neighbours(&self, h: u8, v: u8) -> u8 {
+        self.count_neighbours_matching(h, v, |cs| cs == &CellState::Alive)
+    }
+
+    /// Count the living cells of the Moore neighbourhood (see
+    /// `count_live_neighbours()`) for all cells of a row at once.
+    /// The neighbouring rows are shifted east and west and the
+    /// eight resulting rows are added bit-parallel, 64 cells per step.
+    ///
+    /// # Arguments
+    /// * `v`: vertical coordinate of the row
+    ///
+    /// # Remarks
+    /// Only the first `horizontal_size` counts are set.
+    #[cfg(feature = "packed")]
+    pub fn count_live_neighbours_row(&self, v: u8) -> [u8; HORIZONTAL_MAX] {
+        if v >= self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        let center = self.row_bits(v);
+        let [north, south] = [Direction::North, Direction::South].map(|direction| {
+            match self.neighbour(0, v, direction) {
+                Some((_, nv)) => self.row_bits(nv),
+                None => [0; HORIZONTAL_WORDS],
+            }
+        });
+
+        // bit planes of the counts (0 to 8), added up like binary numbers
+        let mut planes = [[0u64; HORIZONTAL_WORDS]; 4];
+        let mut add = |row: [u64; HORIZONTAL_WORDS]| {
+            let mut carry = row;
+            for plane in planes.iter_mut() {
+                for (bits, c) in plane.iter_mut().zip(carry.iter_mut()) {
+                    let sum = *bits ^ *c;
+                    *c &= *bits;
+                    *bits = sum;
                 }
-                CellState::Dead => {}
             }
-            self.horizontal_byte_iterator_index += 1;
+        };
+        for row in [north, south] {
+            add(row);
         }
-        Some(rbyte)
+        for row in [north, center, south] {
+            add(self.shift_row(row, Direction::West));
+            add(self.shift_row(row, Direction::East));
+        }
+
+        let mut counts = [0u8; HORIZONTAL_MAX];
+        for (h, count) in counts
+            .iter_mut()
+            .enumerate()
+            .take(self.horizontal_size as usize)
+        {
+            for (i, plane) in planes.iter().enumerate() {
+                *count |= (((plane[h / 64] >> (h % 64)) & 1) as u8) << i;
+            }
+        }
+        counts
     }
-}
 
-impl Iterator for Grid {
-    type Item = CellState;
+    /// Get the cells of a row as bits (set = alive),
+    /// cell `h` in bit `h % 64` of word `h / 64`.
+    ///
+    /// # Arguments
+    /// * `v`: vertical coordinate of the row
+    #[cfg(feature = "packed")]
+    fn row_bits(&self, v: u8) -> [u64; HORIZONTAL_WORDS] {
+        let mut bits = [0u64; HORIZONTAL_WORDS];
+        let bytes = (self.horizontal_size as usize).div_ceil(8);
+        for (i, byte) in self.cells[v as usize].iter().take(bytes).enumerate() {
+            // the first cell of a byte is its most significant bit
+            bits[i / 8] |= (byte.reverse_bits() as u64) << (i % 8 * 8);
+        }
+        // drop padding bits beyond the horizontal size
+        let h_size = self.horizontal_size as usize;
+        for (i, word) in bits.iter_mut().enumerate() {
+            let used = h_size.saturating_sub(i * 64).min(64);
+            if used < 64 {
+                *word &= (1u64 << used) - 1;
+            }
+        }
+        bits
+    }
 
-    /// Go over the grid along the rows and return
-    /// the respective state if a cell. This function
-    /// is the basis for the iterator trait.
-    fn next(&mut self) -> Option<CellState> {
-        // check if we are at the end of a row
-        if self.horizontal_cell_iterator_index >= self.horizontal_size as usize {
-            // yes we are, so we go to the next row
-            self.vertical_cell_iterator_index += 1;
-            // and reset the index
-            self.horizontal_cell_iterator_index = 0;
+    /// Move every cell of a row (as bits) to the position of its
+    /// eastern or western neighbour, so each position holds the state
+    /// of its western or eastern neighbour, respectively. Cells wrap
+    /// around or are dropped at the edges, depending on the boundary.
+    ///
+    /// # Arguments
+    /// * `row`: the row as bits, see `row_bits()`
+    /// * `neighbour`: the neighbour (`West` or `East`) each position gets
+    #[cfg(feature = "packed")]
+    fn shift_row(
+        &self,
+        row: [u64; HORIZONTAL_WORDS],
+        neighbour: Direction,
+    ) -> [u64; HORIZONTAL_WORDS] {
+        let last = self.horizontal_size as usize - 1;
+        let bit = |h: usize| (row[h / 64] >> (h % 64)) & 1;
+        let mut shifted = [0u64; HORIZONTAL_WORDS];
+        let wrap = self.horizontal_boundary == BoundaryMode::Wrap;
+        match neighbour {
+            Direction::West => {
+                // towards higher positions
+                let mut carry = 0;
+                for (word, bits) in shifted.iter_mut().zip(row.iter()) {
+                    *word = bits << 1 | carry;
+                    carry = bits >> 63;
+                }
+                shifted[last / 64] &= u64::MAX >> (63 - last % 64);
+                if wrap {
+                    shifted[0] |= bit(last);
+                }
+            }
+            _ => {
+                // towards lower positions
+                let mut carry = 0;
+                for (word, bits) in shifted.iter_mut().zip(row.iter()).rev() {
+                    *word = bits >> 1 | carry;
+                    carry = bits << 63;
+                }
+                if wrap {
+                    shifted[last / 64] |= bit(0) << (last % 64);
+                }
+            }
         }
-        // if we are past the last row
-        if self.vertical_cell_iterator_index >= self.vertical_size as usize {
-            // the iteration stops
-            return None;
+        shifted
+    }
+
+    /// Count the living cells within the square (Chebyshev) neighbourhood
+    /// of the given radius, excluding the cell itself (e.g. for Larger
+    /// than Life). Radius 1 is the Moore neighbourhood.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    /// * `radius`: maximal distance of a neighbour on each axis
+    ///
+    /// # Remarks
+    /// Every cell is counted at most once, even if the neighbourhood
+    /// is larger than the grid and wraps around onto itself. This
+    /// differs from `count_live_neighbours()` on grids with fewer
+    /// than three cells along an axis.
+    #[cfg(feature = "dead-alive-only")]
+    pub fn count_live_in_radius(&self, h: u8, v: u8, radius: u8) -> u16 {
+        if h >= self.horizontal_size {
+            panic!("horizontal coordinate too large")
+        }
+        if v >= self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        let (first_h, width) =
+            Grid::span(h, radius, self.horizontal_size, self.horizontal_boundary);
+        let (first_v, height) = Grid::span(v, radius, self.vertical_size, self.vertical_boundary);
+        let mut count = 0u16;
+        for dv in 0..height {
+            for dh in 0..width {
+                let nh = (first_h + dh).rem_euclid(self.horizontal_size as i16) as u8;
+                let nv = (first_v + dv).rem_euclid(self.vertical_size as i16) as u8;
+                if (nh, nv) != (h, v) && self.get_cellstate(nh, nv) == &CellState::Alive {
+                    count += 1;
+                }
+            }
         }
+        count
+    }
 
-        // get what we want to return
-        let rdata = Some(*self.get_cellstate(
-            self.horizontal_cell_iterator_index as u8,
-            self.vertical_cell_iterator_index as u8,
-        ));
-        // increment tracking indexes
-        self.horizontal_cell_iterator_index += 1;
+    /// Count the cells of the Moore neighbourhood (excluding the
+    /// cell itself) satisfying a predicate, e.g. being in a given
+    /// state. Neighbours beyond a fixed boundary are not counted.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    /// * `predicate`: `true` counts the neighbour
+    ///
+    /// # Remarks
+    /// Like `count_live_neighbours()` each of the eight neighbour
+    /// positions is counted on its own.
+    pub fn count_neighbours_matching<P: Fn(&CellState) -> bool>(
+        &self,
+        h: u8,
+        v: u8,
+        predicate: P,
+    ) -> u8 {
+        self.moore_coordinates(h, v)
+            .iter()
+            .flatten()
+            .filter(|hv| predicate(self.get_cellstate_hv(**hv)))
+            .count() as u8
+    }
 
-        rdata
+    /// Check if an odd number of the eight cells of the Moore
+    /// neighbourhood is alive (e.g. for XOR based automata).
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    #[cfg(feature = "dead-alive-only")]
+    pub fn moore_alive_parity(&self, h: u8, v: u8) -> bool {
+        self.count_live_neighbours(h, v) % 2 == 1
     }
-}
 
-/// A universe contains everything you need to enable
-/// Cellular Automata to do their thing.
-#[derive(Copy, Clone)]
-pub struct Universe {
-    /// The current state of the grid.
-    pub grid: Grid,
-    /// Temporary internal grid to calculate new state.
-    shadow: Grid,
-    /// The transformation function / cellular automaton.
-    automaton: fn(u8, u8, &Grid) -> CellState,
-}
+    /// Count the living cells of the 3x3 block around a cell,
+    /// including the cell itself (0 to 9). Totalistic rules use
+    /// this count, while outer totalistic rules (e.g. Conway's
+    /// Game of Life) look at the neighbours only.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    #[cfg(feature = "dead-alive-only")]
+    pub fn count_alive_moore_inclusive(&self, h: u8, v: u8) -> u8 {
+        let center = (self.get_cellstate(h, v) == &CellState::Alive) as u8;
+        self.count_live_neighbours(h, v) + center
+    }
 
-impl Universe {
-    /// Create a new universe with only dead cells.
+    /// Reset every cell to the default (dead) state
+    /// unless the given predicate holds for it.
     ///
     /// # Arguments
-    /// * `h_size`: horizontal dimension/size as number of cells
-    /// * `v_size`: vertical dimension/size as number of cells
-    /// * `rules`: a function mapping a coordinate (and thus the state of a cell) on a grid to a new state
-    pub fn new(h_size: u8, v_size: u8, rules: fn(u8, u8, &Grid) -> CellState) -> Universe {
-        Universe {
-            grid: Grid::new(h_size, v_size),
-            shadow: Grid::new(h_size, v_size),
-            automaton: rules,
+    /// * `f`: predicate on the coordinates and state of a cell, `true` keeps the cell
+    pub fn retain<F: Fn(u8, u8, &CellState) -> bool>(&mut self, f: F) {
+        for h in 0..self.horizontal_size {
+            for v in 0..self.vertical_size {
+                if !f(h, v, self.get_cellstate(h, v)) {
+                    self.set_cellstate(h, v, CellState::default());
+                }
+            }
+        }
+    }
+
+    /// Transform the cells of a rectangular region, leaving the
+    /// rest of the grid untouched. The region wraps around the
+    /// edges of the grid.
+    ///
+    /// # Arguments
+    /// * `top_left`: tuple (horizontal coordinate, vertical coordinate) of the first cell
+    /// * `size`: tuple (horizontal size, vertical size) of the region
+    /// * `f`: a function mapping coordinate and state of a cell to its new state
+    pub fn map_region<F: Fn(u8, u8, CellState) -> CellState>(
+        &mut self,
+        top_left: (u8, u8),
+        size: (u8, u8),
+        f: F,
+    ) {
+        if top_left.0 >= self.horizontal_size || size.0 > self.horizontal_size {
+            panic!("horizontal coordinate too large")
+        }
+        if top_left.1 >= self.vertical_size || size.1 > self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        for dh in 0..size.0 {
+            for dv in 0..size.1 {
+                let h = ((top_left.0 as u16 + dh as u16) % self.horizontal_size as u16) as u8;
+                let v = ((top_left.1 as u16 + dv as u16) % self.vertical_size as u16) as u8;
+                let state = f(h, v, *self.get_cellstate(h, v));
+                self.set_cellstate(h, v, state);
+            }
+        }
+    }
+
+    /// Set all cells of a rectangular region (including both corners)
+    /// to the given state. If a coordinate of the bottom right corner
+    /// is smaller than the one of the top left corner, the region
+    /// wraps around the edges of the grid.
+    ///
+    /// # Arguments
+    /// * `top_left`: tuple (horizontal coordinate, vertical coordinate) of the first cell
+    /// * `bottom_right`: tuple (horizontal coordinate, vertical coordinate) of the last cell
+    /// * `state`: the new state of the cells
+    pub fn fill_region(&mut self, top_left: (u8, u8), bottom_right: (u8, u8), state: CellState) {
+        if bottom_right.0 >= self.horizontal_size {
+            panic!("horizontal coordinate too large")
+        }
+        if bottom_right.1 >= self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        let width =
+            (bottom_right.0 as i16 - top_left.0 as i16).rem_euclid(self.horizontal_size as i16) + 1;
+        let height =
+            (bottom_right.1 as i16 - top_left.1 as i16).rem_euclid(self.vertical_size as i16) + 1;
+        self.map_region(top_left, (width as u8, height as u8), |_, _, _| state);
+    }
+
+    /// Copy a rectangular region of another grid into this grid
+    /// (e.g. to compose a scene from smaller patterns). The region
+    /// wraps around the edges of both grids.
+    ///
+    /// # Arguments
+    /// * `src`: the grid to copy from
+    /// * `src_origin`: tuple (horizontal coordinate, vertical coordinate) of the first cell to copy
+    /// * `size`: tuple (horizontal size, vertical size) of the region
+    /// * `dst_origin`: tuple (horizontal coordinate, vertical coordinate) of the first cell to overwrite
+    pub fn blit(&mut self, src: &Grid, src_origin: (u8, u8), size: (u8, u8), dst_origin: (u8, u8)) {
+        if src_origin.0 >= src.horizontal_size || size.0 > src.horizontal_size {
+            panic!("horizontal coordinate too large")
+        }
+        if src_origin.1 >= src.vertical_size || size.1 > src.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        if dst_origin.0 >= self.horizontal_size || size.0 > self.horizontal_size {
+            panic!("horizontal coordinate too large")
+        }
+        if dst_origin.1 >= self.vertical_size || size.1 > self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        for dh in 0..size.0 as u16 {
+            for dv in 0..size.1 as u16 {
+                let sh = ((src_origin.0 as u16 + dh) % src.horizontal_size as u16) as u8;
+                let sv = ((src_origin.1 as u16 + dv) % src.vertical_size as u16) as u8;
+                let h = ((dst_origin.0 as u16 + dh) % self.horizontal_size as u16) as u8;
+                let v = ((dst_origin.1 as u16 + dv) % self.vertical_size as u16) as u8;
+                self.set_cellstate(h, v, *src.get_cellstate(sh, sv));
+            }
         }
     }
 
-    /// Update the universe according to the given state and rules
-    pub fn update(&mut self) {
-        // calculate new state from original grid and
-        // (temporarily) save in shadow grid
-        for h in 0..self.grid.horizontal_size {
-            for v in 0..self.grid.vertical_size {
-                let state = (self.automaton)(h, v, &self.grid);
-                self.shadow.set_cellstate(h, v, state);
-            }
+    /// Check if the cells are invariant under the given symmetry
+    /// operation (e.g. to classify patterns).
+    ///
+    /// # Arguments
+    /// * `sym`: the symmetry operation
+    pub fn is_symmetric(&self, sym: Symmetry) -> bool {
+        let h_last = self.horizontal_size - 1;
+        let v_last = self.vertical_size - 1;
+        for h in 0..self.horizontal_size {
+            for v in 0..self.vertical_size {
+                let (mh, mv) = match sym {
+                    Symmetry::Horizontal => (h_last - h, v),
+                    Symmetry::Vertical => (h, v_last - v),
+                    Symmetry::Rotation180 => (h_last - h, v_last - v),
+                };
+                if self.get_cellstate(h, v) != self.get_cellstate(mh, mv) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Mirror the grid in place by swapping left and right
+    /// (see `Symmetry::Horizontal`).
+    pub fn flip_horizontal(&mut self) {
+        let h_last = self.horizontal_size - 1;
+        for h in 0..self.horizontal_size / 2 {
+            for v in 0..self.vertical_size {
+                let left = *self.get_cellstate(h, v);
+                let right = *self.get_cellstate(h_last - h, v);
+                self.set_cellstate(h, v, right);
+                self.set_cellstate(h_last - h, v, left);
+            }
+        }
+        #[cfg(feature = "alloc")]
+        self.remap_active_mask(|h, v| (h_last - h, v));
+    }
+
+    /// Mirror the grid in place by swapping top and bottom
+    /// (see `Symmetry::Vertical`).
+    pub fn flip_vertical(&mut self) {
+        let v_last = self.vertical_size - 1;
+        for h in 0..self.horizontal_size {
+            for v in 0..self.vertical_size / 2 {
+                let top = *self.get_cellstate(h, v);
+                let bottom = *self.get_cellstate(h, v_last - v);
+                self.set_cellstate(h, v, bottom);
+                self.set_cellstate(h, v_last - v, top);
+            }
+        }
+        #[cfg(feature = "alloc")]
+        self.remap_active_mask(|h, v| (h, v_last - v));
+    }
+
+    /// Rotate the grid in place by 90 degrees clockwise, i.e. the
+    /// top row becomes the rightmost column.
+    ///
+    /// # Remarks
+    /// Only square grids can be rotated in place, otherwise
+    /// `GridError::NotSquare` is returned and nothing changes.
+    pub fn rotate_cw(&mut self) -> Result<(), GridError> {
+        if self.horizontal_size != self.vertical_size {
+            return Err(GridError::NotSquare);
+        }
+        let last = self.horizontal_size - 1;
+        let source = *self;
+        for h in 0..self.horizontal_size {
+            for v in 0..self.vertical_size {
+                self.set_cellstate(last - v, h, *source.get_cellstate(h, v));
+            }
+        }
+        #[cfg(feature = "alloc")]
+        self.remap_active_mask(|h, v| (last - v, h));
+        Ok(())
+    }
+
+    /// Cyclically shift all cells by the given offsets. Cells
+    /// leaving the grid on one side re-enter on the opposite one
+    /// (like `roll` in numpy).
+    ///
+    /// # Arguments
+    /// * `dh`: horizontal offset, positive values shift towards the east
+    /// * `dv`: vertical offset, positive values shift towards the south
+    pub fn roll(&mut self, dh: i16, dv: i16) {
+        let source = *self;
+        let h_size = self.horizontal_size as i32;
+        let v_size = self.vertical_size as i32;
+        for h in 0..self.horizontal_size {
+            for v in 0..self.vertical_size {
+                let new_h = (h as i32 + dh as i32).rem_euclid(h_size) as u8;
+                let new_v = (v as i32 + dv as i32).rem_euclid(v_size) as u8;
+                self.set_cellstate(new_h, new_v, *source.get_cellstate(h, v));
+            }
+        }
+        #[cfg(feature = "alloc")]
+        self.remap_active_mask(|h, v| {
+            (
+                (h as i32 + dh as i32).rem_euclid(h_size) as u8,
+                (v as i32 + dv as i32).rem_euclid(v_size) as u8,
+            )
+        });
+    }
+
+    /// Place a glider (Game of Life) into the grid. The glider
+    /// occupies five cells of a 3x3 box, cells beyond the edges wrap
+    /// around. Other cells in the box are not changed.
+    ///
+    /// # Arguments
+    /// * `offset`: tuple (horizontal, vertical) coordinate of the upper left corner of the box
+    /// * `orientation`: the direction the glider travels
+    ///
+    /// # Panics
+    /// If the offset lies outside of the grid.
+    #[cfg(feature = "conway")]
+    pub fn place_glider(&mut self, offset: (u8, u8), orientation: Orientation) {
+        if offset.0 >= self.horizontal_size {
+            panic!("horizontal coordinate too large")
+        }
+        if offset.1 >= self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        // travelling south east, other orientations are mirrored
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        for (h, v) in glider {
+            let (h, v) = match orientation {
+                Orientation::SouthEast => (h, v),
+                Orientation::SouthWest => (2 - h, v),
+                Orientation::NorthEast => (h, 2 - v),
+                Orientation::NorthWest => (2 - h, 2 - v),
+            };
+            let h = (offset.0 as u16 + h) % self.horizontal_size as u16;
+            let v = (offset.1 as u16 + v) % self.vertical_size as u16;
+            self.set_cellstate(h as u8, v as u8, CellState::Alive);
+        }
+    }
+
+    /// Fill the grid with random noise, e.g. to explore a rule.
+    /// A cell becomes alive if a pseudo random byte is below
+    /// `alive_probability`, the same seed always yields the same grid.
+    ///
+    /// # Arguments
+    /// * `seed`: seed of the (xorshift64*) pseudo random number generator
+    /// * `alive_probability`: chance of a living cell in 256th, 0 leaves all cells dead
+    #[cfg(feature = "dead-alive-only")]
+    pub fn randomize(&mut self, seed: u64, alive_probability: u8) {
+        // the generator would only produce zeros from a zero state
+        let mut state = if seed == 0 { FNV_OFFSET_BASIS } else { seed };
+        for v in 0..self.vertical_size {
+            for h in 0..self.horizontal_size {
+                let byte = (xorshift64star(&mut state) >> 56) as u8;
+                let cs = if byte < alive_probability {
+                    CellState::Alive
+                } else {
+                    CellState::Dead
+                };
+                self.set_cellstate(h, v, cs);
+            }
+        }
+    }
+
+    /// Shift every cell by the given offsets with wrapping at all
+    /// edges, e.g. to re-center a drifting spaceship. This is the
+    /// same as `roll()`.
+    ///
+    /// # Arguments
+    /// * `dh`: horizontal offset, positive values shift towards the east
+    /// * `dv`: vertical offset, positive values shift towards the south
+    pub fn translate(&mut self, dh: i16, dv: i16) {
+        self.roll(dh, dv);
+    }
+
+    /// Calculate a (FNV-1a) hash over the dimensions and cell states
+    /// of the grid. Grids with equal cells have equal fingerprints.
+    /// This is the same value as `checksum()`.
+    pub fn fingerprint(&self) -> u64 {
+        self.checksum()
+    }
+
+    /// Calculate a checksum of the grid for compact comparisons
+    /// (e.g. instead of storing whole grids). Equal grids have equal
+    /// checksums.
+    ///
+    /// # Remarks
+    /// The checksum is a 64 bit FNV-1a hash over the horizontal and
+    /// vertical size followed by one byte per cell (`CellState::to_byte()`)
+    /// row by row. This layout is fixed, so checksums can be stored
+    /// and compared across runs, platforms and versions of this crate.
+    pub fn checksum(&self) -> u64 {
+        let mut hash = fnv1a(
+            FNV_OFFSET_BASIS,
+            &[self.horizontal_size, self.vertical_size],
+        );
+        for v in 0..self.vertical_size {
+            for h in 0..self.horizontal_size {
+                hash = fnv1a(hash, &[self.get_cellstate(h, v).to_byte()]);
+            }
+        }
+        hash
+    }
+
+    /// Find the first cell (row by row) in which two grids of
+    /// equal dimensions differ. Returns `None` for equal grids.
+    ///
+    /// # Arguments
+    /// * `other`: the grid to compare with
+    pub fn first_difference(&self, other: &Grid) -> Option<(u8, u8)> {
+        if self.horizontal_size != other.horizontal_size
+            || self.vertical_size != other.vertical_size
+        {
+            panic!("grid dimensions do not match")
+        }
+        for v in 0..self.vertical_size {
+            for h in 0..self.horizontal_size {
+                if self.get_cellstate(h, v) != other.get_cellstate(h, v) {
+                    return Some((h, v));
+                }
+            }
+        }
+        None
+    }
+
+    /// Apply changes written by `Universe::serialize_delta()`. The
+    /// delta consists of three bytes per changed cell: horizontal
+    /// coordinate, vertical coordinate and the new state. Nothing
+    /// is modified if the delta is invalid.
+    ///
+    /// # Arguments
+    /// * `delta`: the encoded changes
+    pub fn apply_delta(&mut self, delta: &[u8]) -> Result<(), ParseError> {
+        if !delta.chunks_exact(3).remainder().is_empty() {
+            return Err(ParseError::Truncated);
+        }
+        for entry in delta.chunks_exact(3) {
+            if entry[0] >= self.horizontal_size || entry[1] >= self.vertical_size {
+                return Err(ParseError::OutOfBounds);
+            }
+            if CellState::from_byte(entry[2]).is_none() {
+                return Err(ParseError::InvalidState(entry[2]));
+            }
+        }
+        for entry in delta.chunks_exact(3) {
+            if let Some(state) = CellState::from_byte(entry[2]) {
+                self.set_cellstate(entry[0], entry[1], state);
+            }
+        }
+        Ok(())
+    }
+
+    /// Change the dimensions of the grid while keeping the existing
+    /// cells centered. New margins are filled with default (dead)
+    /// cells, cells outside of a shrunken grid are dropped. An
+    /// active mask (see `set_active_mask()`) is removed.
+    /// Invalid dimensions (see `try_new()`) leave the grid untouched.
+    ///
+    /// # Arguments
+    /// * `new_h`: new horizontal dimension/size as number of cells
+    /// * `new_v`: new vertical dimension/size as number of cells
+    pub fn resize_preserving_center(&mut self, new_h: u8, new_v: u8) -> Result<(), GridError> {
+        let mut resized = Grid::try_new(new_h, new_v)?;
+        resized.horizontal_boundary = self.horizontal_boundary;
+        resized.vertical_boundary = self.vertical_boundary;
+        let dh = (new_h as i32 - self.horizontal_size as i32) / 2;
+        let dv = (new_v as i32 - self.vertical_size as i32) / 2;
+
+        for h in 0..new_h {
+            for v in 0..new_v {
+                let old_h = h as i32 - dh;
+                let old_v = v as i32 - dv;
+                if (0..self.horizontal_size as i32).contains(&old_h)
+                    && (0..self.vertical_size as i32).contains(&old_v)
+                {
+                    resized.set_cellstate(h, v, *self.get_cellstate(old_h as u8, old_v as u8));
+                }
+            }
+        }
+        *self = resized;
+        Ok(())
+    }
+
+    /// Count the living cells on the grid.
+    ///
+    /// # Remarks
+    /// Counts are returned as `u32` since a grid of maximal size
+    /// (255x255) holds up to 65025 cells, which overflows a `u16`.
+    #[cfg(feature = "dead-alive-only")]
+    pub fn population(&self) -> u32 {
+        let mut count = 0u32;
+        for h in 0..self.horizontal_size {
+            for v in 0..self.vertical_size {
+                if self.get_cellstate(h, v) == &CellState::Alive {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Check if no cell on the grid is alive.
+    #[cfg(feature = "dead-alive-only")]
+    pub fn is_extinct(&self) -> bool {
+        for h in 0..self.horizontal_size {
+            for v in 0..self.vertical_size {
+                if self.get_cellstate(h, v) == &CellState::Alive {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Render the grid row by row with custom characters
+    /// for living and dead cells (e.g. `format!("{}", g.display_with('#', ' '))`).
+    /// The `Display` implementation of a grid uses `O` and `.`.
+    ///
+    /// # Arguments
+    /// * `alive`: character of a living cell
+    /// * `dead`: character of a dead cell
+    #[cfg(feature = "dead-alive-only")]
+    pub fn display_with(&self, alive: char, dead: char) -> GridDisplay<'_> {
+        GridDisplay {
+            grid: self,
+            alive,
+            dead,
+            framed: false,
+        }
+    }
+
+    /// Render the grid row by row into a string,
+    /// e.g. with block characters for living cells.
+    ///
+    /// # Arguments
+    /// * `style`: the characters of living and dead cells
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    pub fn render_with(&self, style: RenderStyle) -> alloc::string::String {
+        alloc::format!("{}", self.display_with(style.alive, style.dead))
+    }
+
+    /// Render the grid inside a box-drawing border, living cells
+    /// as `O` and dead cells as `.` (use `display_with()` and
+    /// `GridDisplay::framed()` for other characters).
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    pub fn render_framed(&self) -> alloc::string::String {
+        alloc::format!("{}", self.display_with('O', '.').framed())
+    }
+
+    /// Count the living cells in a row.
+    ///
+    /// # Arguments
+    /// * `v`: vertical coordinate of the row
+    ///
+    /// # Remarks
+    /// A row holds at most 255 cells, so the count fits into a `u8`.
+    #[cfg(feature = "dead-alive-only")]
+    pub fn count_alive_in_row(&self, v: u8) -> u8 {
+        if v >= self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        let mut count = 0u8;
+        for h in 0..self.horizontal_size {
+            if self.get_cellstate(h, v) == &CellState::Alive {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Count the living cells in a column.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate of the column
+    ///
+    /// # Remarks
+    /// A column holds at most 255 cells, so the count fits into a `u8`.
+    #[cfg(feature = "dead-alive-only")]
+    pub fn count_alive_in_column(&self, h: u8) -> u8 {
+        if h >= self.horizontal_size {
+            panic!("horizontal coordinate too large")
+        }
+        let mut count = 0u8;
+        for v in 0..self.vertical_size {
+            if self.get_cellstate(h, v) == &CellState::Alive {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Pack a row into bytes, eight cells per byte (first cell in the
+    /// most significant bit, dead = 0, alive = 1). The last used byte
+    /// is padded with zeros, the remaining bytes are zero.
+    ///
+    /// # Arguments
+    /// * `v`: vertical coordinate of the row
+    #[cfg(feature = "dead-alive-only")]
+    pub fn pack_row(&self, v: u8) -> [u8; HORIZONTAL_BYTES] {
+        if v >= self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        let mut bytes = [0u8; HORIZONTAL_BYTES];
+        for h in 0..self.horizontal_size {
+            if self.get_cellstate(h, v) == &CellState::Alive {
+                bytes[h as usize / 8] |= 0b10000000 >> (h % 8);
+            }
+        }
+        bytes
+    }
+
+    /// Set a row from packed bytes, the counterpart to `pack_row()`.
+    /// Bits beyond the horizontal size are ignored.
+    ///
+    /// # Arguments
+    /// * `v`: vertical coordinate of the row
+    /// * `bytes`: the packed cells, at least enough to hold the row
+    ///
+    /// # Panics
+    /// If there are too few bytes for the row.
+    #[cfg(feature = "dead-alive-only")]
+    pub fn unpack_row(&mut self, v: u8, bytes: &[u8]) {
+        if v >= self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        if bytes.len() < (self.horizontal_size as usize).div_ceil(8) {
+            panic!("too few bytes for the row")
+        }
+        for h in 0..self.horizontal_size {
+            let state = if bytes[h as usize / 8] & (0b10000000 >> (h % 8)) == 0 {
+                CellState::Dead
+            } else {
+                CellState::Alive
+            };
+            self.set_cellstate(h, v, state);
+        }
+    }
+
+    /// Create a grid from rows of octets as produced by `cs8_into_u8()`
+    /// (first cell in the most significant bit, dead = 0, alive = 1).
+    /// Each row needs enough bytes to hold `h_size` cells.
+    ///
+    /// # Arguments
+    /// * `h_size`: horizontal dimension/size as number of cells
+    /// * `v_size`: vertical dimension/size as number of cells
+    /// * `rows`: the packed rows, starting at the top
+    #[cfg(feature = "dead-alive-u8-utils")]
+    pub fn from_packed_u8_rows(h_size: u8, v_size: u8, rows: &[&[u8]]) -> Result<Grid, ParseError> {
+        if h_size == 0 || v_size == 0 {
+            return Err(ParseError::InvalidDimensions);
+        }
+        if rows.len() < v_size as usize {
+            return Err(ParseError::Truncated);
+        }
+        let mut g = Grid::new(h_size, v_size);
+        for (v, row) in rows.iter().take(v_size as usize).enumerate() {
+            if row.len() < (h_size as usize).div_ceil(8) {
+                return Err(ParseError::Truncated);
+            }
+            for h in 0..h_size {
+                let states = u8_into_cs8(row[h as usize / 8]);
+                g.set_cellstate(h, v as u8, states[h as usize % 8]);
+            }
+        }
+        Ok(g)
+    }
+
+    /// Create a grid from rows of booleans (alive = `true`).
+    /// The grid is sized to the input.
+    ///
+    /// # Arguments
+    /// * `rows`: the rows starting at the top, all of equal length
+    #[cfg(feature = "dead-alive-only")]
+    pub fn from_bool_rows(rows: &[&[bool]]) -> Result<Grid, GridError> {
+        let v_size = rows.len();
+        let h_size = rows.first().map_or(0, |row| row.len());
+        if h_size == 0 || h_size > HORIZONTAL_MAX || v_size == 0 || v_size > VERTICAL_MAX {
+            return Err(GridError::InvalidDimensions);
+        }
+        if rows.iter().any(|row| row.len() != h_size) {
+            return Err(GridError::NotRectangular);
+        }
+        let mut g = Grid::new(h_size as u8, v_size as u8);
+        for (v, row) in rows.iter().enumerate() {
+            for (h, alive) in row.iter().enumerate() {
+                if *alive {
+                    g.set_cellstate(h as u8, v as u8, CellState::Alive);
+                }
+            }
+        }
+        Ok(g)
+    }
+
+    /// Create a grid from nested vectors. The outer vector holds
+    /// the rows (starting at the top), each inner vector the cells
+    /// of a row (starting at the left).
+    ///
+    /// # Arguments
+    /// * `rows`: the cell states, all rows of equal length
+    #[cfg(feature = "alloc")]
+    pub fn from_nested(rows: Vec<Vec<CellState>>) -> Result<Grid, ParseError> {
+        let v_size = rows.len();
+        let h_size = rows.first().map_or(0, |row| row.len());
+        if h_size == 0 || h_size > HORIZONTAL_MAX || v_size == 0 || v_size > VERTICAL_MAX {
+            return Err(ParseError::InvalidDimensions);
+        }
+        if rows.iter().any(|row| row.len() != h_size) {
+            return Err(ParseError::NotRectangular);
+        }
+        let mut g = Grid::new(h_size as u8, v_size as u8);
+        for (v, row) in rows.iter().enumerate() {
+            for (h, state) in row.iter().enumerate() {
+                g.set_cellstate(h as u8, v as u8, *state);
+            }
+        }
+        Ok(g)
+    }
+
+    /// Export the grid as nested vectors, the counterpart
+    /// to `from_nested()`. The outer vector holds the rows
+    /// (starting at the top), each inner vector the cells of
+    /// a row (starting at the left).
+    #[cfg(feature = "alloc")]
+    pub fn to_nested(&self) -> Vec<Vec<CellState>> {
+        (0..self.vertical_size)
+            .map(|v| {
+                (0..self.horizontal_size)
+                    .map(|h| *self.get_cellstate(h, v))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Export the grid as rows of booleans (alive = `true`),
+    /// the counterpart to `from_bool_rows()`.
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    pub fn to_bool_matrix(&self) -> Vec<Vec<bool>> {
+        (0..self.vertical_size)
+            .map(|v| {
+                (0..self.horizontal_size)
+                    .map(|h| self.get_cellstate(h, v) == &CellState::Alive)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Export the grid as binary PGM (P5) image with one pixel per
+    /// cell: alive cells are white (255), dead cells black (0).
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut image =
+            alloc::format!("P5\n{} {}\n255\n", self.horizontal_size, self.vertical_size)
+                .into_bytes();
+        image.extend(self.iter_cells().map(|(_, _, cs)| match cs {
+            CellState::Alive => 255u8,
+            CellState::Dead => 0u8,
+        }));
+        image
+    }
+
+    /// Number of bytes written by `serialize_to()`.
+    #[cfg(feature = "dead-alive-only")]
+    pub fn packed_len(&self) -> usize {
+        3 + (self.horizontal_size as usize * self.vertical_size as usize).div_ceil(8)
+    }
+
+    /// Write the grid as bytes into a buffer and return the number of
+    /// bytes written (see `packed_len()`). The first byte holds the
+    /// format version, followed by the horizontal and vertical size.
+    /// The cells follow row by row with eight cells per byte (first
+    /// cell in the most significant bit, dead = 0, alive = 1).
+    /// The last byte is padded with zeros.
+    ///
+    /// # Arguments
+    /// * `buf`: the buffer to write to
+    #[cfg(feature = "dead-alive-only")]
+    pub fn serialize_to(&self, buf: &mut [u8]) -> Result<usize, PackError> {
+        let len = self.packed_len();
+        if buf.len() < len {
+            return Err(PackError::BufferTooSmall);
+        }
+        buf[0] = PACKED_FORMAT_VERSION;
+        buf[1] = self.horizontal_size;
+        buf[2] = self.vertical_size;
+        let cells = &mut buf[3..len];
+        cells.fill(0);
+        for (i, (_, _, cs)) in self.iter_cells().enumerate() {
+            if cs == &CellState::Alive {
+                cells[i / 8] |= 0x80u8 >> (i % 8);
+            }
+        }
+        Ok(len)
+    }
+
+    /// Create a grid from bytes written by `serialize_to()`.
+    /// Bytes after the packed grid are ignored.
+    ///
+    /// # Arguments
+    /// * `buf`: the packed grid including its header
+    #[cfg(feature = "dead-alive-only")]
+    pub fn deserialize_from(buf: &[u8]) -> Result<Grid, PackError> {
+        if buf.is_empty() {
+            return Err(PackError::Truncated);
+        }
+        if buf[0] != PACKED_FORMAT_VERSION {
+            return Err(PackError::UnsupportedVersion(buf[0]));
+        }
+        if buf.len() < 3 {
+            return Err(PackError::Truncated);
+        }
+        let (h_size, v_size) = (buf[1], buf[2]);
+        if h_size == 0 || v_size == 0 {
+            return Err(PackError::InvalidDimensions);
+        }
+        let cells = &buf[3..];
+        let cell_count = h_size as usize * v_size as usize;
+        if cells.len() < cell_count.div_ceil(8) {
+            return Err(PackError::Truncated);
+        }
+
+        let mut g = Grid::new(h_size, v_size);
+        for i in 0..cell_count {
+            if cells[i / 8] & (0x80u8 >> (i % 8)) != 0 {
+                g.set_cellstate(
+                    (i % h_size as usize) as u8,
+                    (i / h_size as usize) as u8,
+                    CellState::Alive,
+                );
+            }
+        }
+        Ok(g)
+    }
+
+    /// Export the grid as bytes in the format of `serialize_to()`.
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    pub fn to_packed_bytes(&self) -> Vec<u8> {
+        let mut bytes = alloc::vec![0; self.packed_len()];
+        let _ = self.serialize_to(&mut bytes);
+        bytes
+    }
+
+    /// Create a grid from bytes written by `to_packed_bytes()`.
+    ///
+    /// # Arguments
+    /// * `bytes`: the packed grid including its header
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    pub fn from_packed_bytes(bytes: &[u8]) -> Result<Grid, PackError> {
+        Grid::deserialize_from(bytes)
+    }
+
+    /// Go over the grid row by row and return eight
+    /// cell states as a byte (dead = 0, alive = 1).
+    /// This is an iterator-like utility function.
+    #[cfg(feature = "dead-alive-only")]
+    pub fn next_byte(&mut self) -> Option<u8> {
+        //TODO: check if cell count is a multiple of 8 -> compilation error otherwise
+        let mut rbyte = 0x00u8; // byte to return
+
+        for offset in 0..8u8 {
+            if self.horizontal_byte_iterator_index >= self.horizontal_size as usize {
+                // we stepped over the end of a row so ...
+                // ... we go to the next row ...
+                self.vertical_byte_iterator_index += 1;
+                // ... and reset the index pointer
+                self.horizontal_byte_iterator_index = 0;
+            }
+            if self.vertical_byte_iterator_index >= self.vertical_size as usize {
+                return None;
+            }
+
+            match self.get_cellstate(
+                self.horizontal_byte_iterator_index as u8,
+                self.vertical_byte_iterator_index as u8,
+            ) {
+                CellState::Alive => {
+                    // set bit according to offset
+                    rbyte |= 0x80u8 >> offset;
+                }
+                CellState::Dead => {}
+            }
+            self.horizontal_byte_iterator_index += 1;
+        }
+        Some(rbyte)
+    }
+}
+
+impl Iterator for Grid {
+    type Item = CellState;
+
+    /// Go over the grid along the rows and return
+    /// the respective state if a cell. This function
+    /// is the basis for the iterator trait.
+    fn next(&mut self) -> Option<CellState> {
+        // check if we are at the end of a row
+        if self.horizontal_cell_iterator_index >= self.horizontal_size as usize {
+            // yes we are, so we go to the next row
+            self.vertical_cell_iterator_index += 1;
+            // and reset the index
+            self.horizontal_cell_iterator_index = 0;
+        }
+        // if we are past the last row
+        if self.vertical_cell_iterator_index >= self.vertical_size as usize {
+            // the iteration stops
+            return None;
+        }
+
+        // get what we want to return
+        let rdata = Some(*self.get_cellstate(
+            self.horizontal_cell_iterator_index as u8,
+            self.vertical_cell_iterator_index as u8,
+        ));
+        // increment tracking indexes
+        self.horizontal_cell_iterator_index += 1;
+
+        rdata
+    }
+}
+
+impl PartialEq for Grid {
+    /// Grids are equal if they have the same dimensions and cell states.
+    /// Other settings (e.g. boundaries or the active mask) are ignored.
+    fn eq(&self, other: &Grid) -> bool {
+        self.horizontal_size == other.horizontal_size
+            && self.vertical_size == other.vertical_size
+            && self.first_difference(other).is_none()
+    }
+}
+
+impl Eq for Grid {}
+
+impl core::hash::Hash for Grid {
+    /// Hash the dimensions and cell states, consistent with `PartialEq`.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write_u8(self.horizontal_size);
+        state.write_u8(self.vertical_size);
+        for v in 0..self.vertical_size {
+            for h in 0..self.horizontal_size {
+                state.write_u8(self.get_cellstate(h, v).to_byte());
+            }
+        }
+    }
+}
+
+impl core::ops::Index<(u8, u8)> for Grid {
+    type Output = CellState;
+
+    /// Retrieve a cell state via `grid[(h, v)]`.
+    ///
+    /// # Panics
+    /// If the coordinate lies outside of the grid.
+    fn index(&self, hv: (u8, u8)) -> &CellState {
+        self.get_cellstate_hv(hv)
+    }
+}
+
+#[cfg(feature = "dead-alive-only")]
+impl core::fmt::Display for Grid {
+    /// Render the grid row by row, living cells as `O`
+    /// and dead cells as `.`, every row ends with a newline.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let style = RenderStyle::default();
+        self.display_with(style.alive, style.dead).fmt(f)
+    }
+}
+
+/// Characters to render cells with, see `Grid::render_with()`.
+#[cfg(feature = "dead-alive-only")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RenderStyle {
+    /// Character of a living cell.
+    pub alive: char,
+    /// Character of a dead cell.
+    pub dead: char,
+}
+
+#[cfg(feature = "dead-alive-only")]
+impl Default for RenderStyle {
+    /// Living cells as `O`, dead cells as `.`.
+    fn default() -> Self {
+        RenderStyle {
+            alive: 'O',
+            dead: '.',
+        }
+    }
+}
+
+/// Renders a grid with custom characters, see `Grid::display_with()`.
+#[cfg(feature = "dead-alive-only")]
+pub struct GridDisplay<'a> {
+    /// The grid to render.
+    grid: &'a Grid,
+    /// Character of a living cell.
+    alive: char,
+    /// Character of a dead cell.
+    dead: char,
+    /// Draw a border around the grid.
+    framed: bool,
+}
+
+#[cfg(feature = "dead-alive-only")]
+impl GridDisplay<'_> {
+    /// Draw a box-drawing border around the grid.
+    pub fn framed(mut self) -> Self {
+        self.framed = true;
+        self
+    }
+
+    /// Write the horizontal part of the border.
+    ///
+    /// # Arguments
+    /// * `f`: the output
+    /// * `left`: the left corner
+    /// * `right`: the right corner
+    fn write_edge(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        left: char,
+        right: char,
+    ) -> core::fmt::Result {
+        use core::fmt::Write;
+        f.write_char(left)?;
+        for _ in 0..self.grid.horizontal_size {
+            f.write_char('─')?;
+        }
+        f.write_char(right)?;
+        f.write_char('\n')
+    }
+}
+
+#[cfg(feature = "dead-alive-only")]
+impl core::fmt::Display for GridDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use core::fmt::Write;
+        if self.framed {
+            self.write_edge(f, '┌', '┐')?;
+        }
+        for v in 0..self.grid.vertical_size {
+            if self.framed {
+                f.write_char('│')?;
+            }
+            for h in 0..self.grid.horizontal_size {
+                f.write_char(match self.grid.get_cellstate(h, v) {
+                    CellState::Alive => self.alive,
+                    CellState::Dead => self.dead,
+                })?;
+            }
+            if self.framed {
+                f.write_char('│')?;
+            }
+            f.write_char('\n')?;
+        }
+        if self.framed {
+            self.write_edge(f, '└', '┘')?;
+        }
+        Ok(())
+    }
+}
+
+/// The transformation function / cellular automaton of a universe.
+enum Automaton<C, R> {
+    /// A rule (function or closure) depending on the grid only.
+    Plain(R),
+    /// A rule additionally reading a user supplied context.
+    Contextual(fn(u8, u8, &Grid, &C) -> CellState),
+    /// A Life-like rule given by the masks of the neighbour counts
+    /// for birth and survival (see `rules::count_mask()`).
+    #[cfg(feature = "fast-life")]
+    LifeLike {
+        /// Neighbour counts bringing a dead cell to life.
+        birth: u16,
+        /// Neighbour counts keeping a living cell alive.
+        survival: u16,
+    },
+}
+
+impl<C, R: Clone> Clone for Automaton<C, R> {
+    fn clone(&self) -> Self {
+        match self {
+            Automaton::Plain(rules) => Automaton::Plain(rules.clone()),
+            Automaton::Contextual(rules) => Automaton::Contextual(*rules),
+            #[cfg(feature = "fast-life")]
+            Automaton::LifeLike { birth, survival } => Automaton::LifeLike {
+                birth: *birth,
+                survival: *survival,
+            },
+        }
+    }
+}
+
+impl<C, R: Copy> Copy for Automaton<C, R> {}
+
+/// A universe contains everything you need to enable
+/// Cellular Automata to do their thing.
+///
+/// # Remarks
+/// The optional context `C` holds parameters for rules
+/// created via `Universe::new_with_context()`. The type `R`
+/// of the rules is a function pointer unless a closure is
+/// passed via `Universe::new_with_closure()`.
+#[derive(Copy, Clone)]
+pub struct Universe<C = (), R = fn(u8, u8, &Grid) -> CellState> {
+    /// The current state of the grid.
+    pub grid: Grid,
+    /// Temporary internal grid to calculate new state.
+    shadow: Grid,
+    /// The grid `Universe::reset()` returns to, captured
+    /// by the first update (and by `Universe::reset_to()`).
+    initial: Grid,
+    /// The transformation function / cellular automaton.
+    automaton: Automaton<C, R>,
+    /// Parameters passed to a contextual rule.
+    context: C,
+    /// Updates are skipped while the universe is paused.
+    paused: bool,
+    /// Number of updates run so far.
+    generation: u64,
+    /// Number of updates each living cell has survived.
+    #[cfg(feature = "age-tracking")]
+    ages: [[u16; VERTICAL_MAX]; HORIZONTAL_MAX],
+    /// Population after each update, the oldest first.
+    #[cfg(feature = "history")]
+    history: [u32; HISTORY_CAPACITY],
+    /// Number of recorded populations.
+    #[cfg(feature = "history")]
+    history_len: usize,
+}
+
+impl Universe {
+    /// Create a new universe with only dead cells.
+    ///
+    /// # Arguments
+    /// * `h_size`: horizontal dimension/size as number of cells
+    /// * `v_size`: vertical dimension/size as number of cells
+    /// * `rules`: a function mapping a coordinate (and thus the state of a cell) on a grid to a new state
+    pub fn new(h_size: u8, v_size: u8, rules: fn(u8, u8, &Grid) -> CellState) -> Universe {
+        Universe {
+            grid: Grid::new(h_size, v_size),
+            shadow: Grid::new(h_size, v_size),
+            initial: Grid::new(h_size, v_size),
+            automaton: Automaton::Plain(rules),
+            context: (),
+            paused: false,
+            generation: 0,
+            #[cfg(feature = "age-tracking")]
+            ages: [[0; VERTICAL_MAX]; HORIZONTAL_MAX],
+            #[cfg(feature = "history")]
+            history: [0; HISTORY_CAPACITY],
+            #[cfg(feature = "history")]
+            history_len: 0,
+        }
+    }
+
+    /// Create a new universe with only dead cells following a
+    /// Life-like rule (see `rules::life_like()`), e.g.
+    /// `Universe::new_life_like(32, 32, &[3, 6], &[2, 3])` for
+    /// HighLife (B36/S23). `update_fast()` speeds up these universes.
+    ///
+    /// # Arguments
+    /// * `h_size`: horizontal dimension/size as number of cells
+    /// * `v_size`: vertical dimension/size as number of cells
+    /// * `birth`: neighbour counts bringing a dead cell to life
+    /// * `survival`: neighbour counts keeping a living cell alive
+    ///
+    /// # Panics
+    /// If a neighbour count is larger than 8.
+    #[cfg(feature = "fast-life")]
+    pub fn new_life_like(h_size: u8, v_size: u8, birth: &[u8], survival: &[u8]) -> Universe {
+        Universe {
+            grid: Grid::new(h_size, v_size),
+            shadow: Grid::new(h_size, v_size),
+            initial: Grid::new(h_size, v_size),
+            automaton: Automaton::LifeLike {
+                birth: rules::count_mask(birth),
+                survival: rules::count_mask(survival),
+            },
+            context: (),
+            paused: false,
+            generation: 0,
+            #[cfg(feature = "age-tracking")]
+            ages: [[0; VERTICAL_MAX]; HORIZONTAL_MAX],
+            #[cfg(feature = "history")]
+            history: [0; HISTORY_CAPACITY],
+            #[cfg(feature = "history")]
+            history_len: 0,
+        }
+    }
+}
+
+impl<R: Fn(u8, u8, &Grid) -> CellState> Universe<(), R> {
+    /// Create a new universe with only dead cells whose rules
+    /// are a closure, e.g. capturing parameters of the rules.
+    ///
+    /// # Arguments
+    /// * `h_size`: horizontal dimension/size as number of cells
+    /// * `v_size`: vertical dimension/size as number of cells
+    /// * `rules`: a closure mapping a coordinate (and thus the state of a cell) on a grid to a new state
+    pub fn new_with_closure(h_size: u8, v_size: u8, rules: R) -> Universe<(), R> {
+        Universe {
+            grid: Grid::new(h_size, v_size),
+            shadow: Grid::new(h_size, v_size),
+            initial: Grid::new(h_size, v_size),
+            automaton: Automaton::Plain(rules),
+            context: (),
+            paused: false,
+            generation: 0,
+            #[cfg(feature = "age-tracking")]
+            ages: [[0; VERTICAL_MAX]; HORIZONTAL_MAX],
+            #[cfg(feature = "history")]
+            history: [0; HISTORY_CAPACITY],
+            #[cfg(feature = "history")]
+            history_len: 0,
+        }
+    }
+}
+
+impl<C> Universe<C> {
+    /// Create a new universe with only dead cells whose rules
+    /// additionally get a context, e.g. parameters changing
+    /// between updates.
+    ///
+    /// # Arguments
+    /// * `h_size`: horizontal dimension/size as number of cells
+    /// * `v_size`: vertical dimension/size as number of cells
+    /// * `ctx`: the context passed to the rules
+    /// * `rules`: a function mapping a coordinate on a grid and the context to a new state
+    pub fn new_with_context(
+        h_size: u8,
+        v_size: u8,
+        ctx: C,
+        rules: fn(u8, u8, &Grid, &C) -> CellState,
+    ) -> Universe<C> {
+        Universe {
+            grid: Grid::new(h_size, v_size),
+            shadow: Grid::new(h_size, v_size),
+            initial: Grid::new(h_size, v_size),
+            automaton: Automaton::Contextual(rules),
+            context: ctx,
+            paused: false,
+            generation: 0,
+            #[cfg(feature = "age-tracking")]
+            ages: [[0; VERTICAL_MAX]; HORIZONTAL_MAX],
+            #[cfg(feature = "history")]
+            history: [0; HISTORY_CAPACITY],
+            #[cfg(feature = "history")]
+            history_len: 0,
+        }
+    }
+}
+
+impl<C, R: Fn(u8, u8, &Grid) -> CellState> Universe<C, R> {
+    /// Get the context passed to the rules.
+    pub fn context(&self) -> &C {
+        &self.context
+    }
+
+    /// Get the context passed to the rules for modification.
+    pub fn context_mut(&mut self) -> &mut C {
+        &mut self.context
+    }
+
+    /// Apply the rules to a cell of the current grid.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    fn apply_rules(&self, h: u8, v: u8) -> CellState {
+        match self.automaton {
+            Automaton::Plain(ref rules) => rules(h, v, &self.grid),
+            Automaton::Contextual(rules) => rules(h, v, &self.grid, &self.context),
+            #[cfg(feature = "fast-life")]
+            Automaton::LifeLike { birth, survival } => rules::life_like_state(
+                birth,
+                survival,
+                *self.grid.get_cellstate(h, v),
+                self.grid.count_live_neighbours(h, v),
+            ),
+        }
+    }
+
+    /// Calculate the state a cell would have after the next
+    /// update without modifying the universe.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    pub fn evaluate_cell(&self, h: u8, v: u8) -> CellState {
+        if h >= self.grid.horizontal_size {
+            panic!("horizontal coordinate too large")
+        }
+        if v >= self.grid.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        self.apply_rules(h, v)
+    }
+
+    /// Replace the grid (e.g. with the initial seed to re-run
+    /// a simulation) while keeping the rules and the context.
+    /// The generation counter and tracked cell ages are reset,
+    /// `reset()` returns to this grid afterwards.
+    ///
+    /// # Arguments
+    /// * `grid`: the new grid
+    ///
+    /// # Panics
+    /// If the dimensions of `grid` differ from the universe.
+    pub fn reset_to(&mut self, grid: Grid) {
+        if grid.horizontal_size != self.grid.horizontal_size
+            || grid.vertical_size != self.grid.vertical_size
+        {
+            panic!("grid dimensions do not match")
+        }
+        self.grid = grid;
+        self.initial = grid;
+        self.generation = 0;
+        #[cfg(feature = "age-tracking")]
+        {
+            self.ages = [[0; VERTICAL_MAX]; HORIZONTAL_MAX];
+        }
+        #[cfg(feature = "history")]
+        {
+            self.history_len = 0;
+        }
+    }
+
+    /// Return to the starting grid, i.e. the grid right before the
+    /// first update (e.g. seeded via `grid.randomize()`), to re-run
+    /// an experiment from its seed. The generation counter and
+    /// tracked cell ages are reset.
+    ///
+    /// # Remarks
+    /// The starting grid is captured by the first update after
+    /// creation, `reset()` or `reset_to()`. Before that the grid
+    /// is kept as it is.
+    pub fn reset(&mut self) {
+        let initial = if self.generation == 0 {
+            self.grid
+        } else {
+            self.initial
+        };
+        self.reset_to(initial);
+    }
+
+    /// Pause or resume the universe. While paused, updates
+    /// leave the grid untouched.
+    ///
+    /// # Arguments
+    /// * `paused`: `true` pauses, `false` resumes the universe
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Check if the universe is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Get the number of updates run so far
+    /// (updates skipped while paused do not count).
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Update the universe according to the given state and rules
+    pub fn update(&mut self) {
+        if self.paused {
+            return;
+        }
+        // calculate new state from original grid and
+        // (temporarily) save in shadow grid
+        for h in 0..self.grid.horizontal_size {
+            for v in 0..self.grid.vertical_size {
+                let state = self.next_state(h, v);
+                self.store_state(h, v, state);
+            }
+        }
+
+        self.publish_shadow();
+    }
+
+    /// Calculate the new state of a cell, cells outside
+    /// of the active mask keep the default state.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    fn next_state(&self, h: u8, v: u8) -> CellState {
+        if self.grid.is_active(h, v) {
+            self.apply_rules(h, v)
+        } else {
+            CellState::default()
+        }
+    }
+
+    /// Store the new state of a cell in the shadow grid.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    /// * `state`: the new state of the cell
+    fn store_state(&mut self, h: u8, v: u8, state: CellState) {
+        #[cfg(feature = "age-tracking")]
+        self.track_age(h, v, state);
+        self.shadow.set_cellstate(h, v, state);
+    }
+
+    /// Make the new (shadow) state public and count the generation.
+    /// The shadow grid then holds the previous generation. Only the
+    /// cells are swapped to keep the grid settings (e.g. boundaries).
+    fn publish_shadow(&mut self) {
+        self.capture_initial();
+        core::mem::swap(&mut self.grid.cells, &mut self.shadow.cells);
+        self.finish_step();
+    }
+
+    /// Keep the grid as starting grid of `reset()`
+    /// if no update has been run yet.
+    fn capture_initial(&mut self) {
+        if self.generation == 0 {
+            self.initial = self.grid;
+        }
+    }
+
+    /// Count a finished update: advance the generation
+    /// and record the population.
+    fn finish_step(&mut self) {
+        self.generation += 1;
+        #[cfg(feature = "history")]
+        self.record_population();
+    }
+
+    /// Update the universe like `update()`. Life-like universes (see
+    /// `new_life_like()`) count the living neighbours of all cells once
+    /// per generation by adding each living cell to its neighbours,
+    /// which saves most neighbour reads on sparse grids. All other
+    /// universes are updated by `update()`.
+    #[cfg(feature = "fast-life")]
+    pub fn update_fast(&mut self) {
+        let (birth, survival) = match self.automaton {
+            Automaton::LifeLike { birth, survival } => (birth, survival),
+            _ => return self.update(),
+        };
+        if self.paused {
+            return;
+        }
+        let mut counts = [[0u8; HORIZONTAL_MAX]; VERTICAL_MAX];
+        for (h, v) in self.grid.iter_alive() {
+            for &(nh, nv) in self.grid.moore_coordinates(h, v).iter().flatten() {
+                counts[nv as usize][nh as usize] += 1;
+            }
+        }
+        for h in 0..self.grid.horizontal_size {
+            for v in 0..self.grid.vertical_size {
+                let state = if self.grid.is_active(h, v) {
+                    rules::life_like_state(
+                        birth,
+                        survival,
+                        *self.grid.get_cellstate(h, v),
+                        counts[v as usize][h as usize],
+                    )
+                } else {
+                    CellState::default()
+                };
+                self.store_state(h, v, state);
+            }
+        }
+
+        self.publish_shadow();
+    }
+
+    /// Count the cells an update would change, without
+    /// modifying the universe. The pause state is ignored,
+    /// i.e. the rules are previewed as if the universe runs.
+    pub fn preview_change_count(&self) -> u32 {
+        let mut count = 0u32;
+        for h in 0..self.grid.horizontal_size {
+            for v in 0..self.grid.vertical_size {
+                let state = self.next_state(h, v);
+                if &state != self.grid.get_cellstate(h, v) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Check if no cell of the universe is alive.
+    #[cfg(feature = "dead-alive-only")]
+    pub fn is_extinct(&self) -> bool {
+        self.grid.is_extinct()
+    }
+
+    /// Update the universe until no living cell is left.
+    /// Returns the number of updates after which the grid
+    /// became empty (0 if it already is), or `None` if cells
+    /// are still alive after `max_steps` updates.
+    ///
+    /// # Arguments
+    /// * `max_steps`: maximum number of updates to run
+    #[cfg(feature = "dead-alive-only")]
+    pub fn update_until_extinct(&mut self, max_steps: u32) -> Option<u32> {
+        if self.grid.is_extinct() {
+            return Some(0);
+        }
+        for step in 1..=max_steps {
+            self.update();
+            if self.grid.is_extinct() {
+                return Some(step);
+            }
+        }
+        None
+    }
+
+    /// Update the universe until a generation equals the previous
+    /// one (fixed point, e.g. a still life). Returns the number of
+    /// updates after which the grid stopped changing (0 if it already
+    /// was stable), or `None` if it still changes after `max_generations`
+    /// updates. A paused universe counts as stable.
+    ///
+    /// # Arguments
+    /// * `max_generations`: maximum number of updates to run
+    pub fn update_until_stable(&mut self, max_generations: usize) -> Option<usize> {
+        for step in 0..max_generations {
+            let previous = self.grid;
+            self.update();
+            if self.grid == previous {
+                return Some(step);
+            }
+        }
+        None
+    }
+
+    /// Update the universe until a generation repeats one of the last
+    /// `max_period` generations (e.g. an oscillator). Returns the
+    /// period, i.e. the distance to the repeated generation (1 for
+    /// a still life), or `None` if no repetition occurs within
+    /// `max_generations` updates. A paused universe has period 1.
+    ///
+    /// # Arguments
+    /// * `max_period`: maximal period to detect
+    /// * `max_generations`: maximum number of updates to run
+    ///
+    /// # Remarks
+    /// Up to `max_period` grids are kept for comparison.
+    #[cfg(feature = "alloc")]
+    pub fn detect_cycle(&mut self, max_period: usize, max_generations: usize) -> Option<usize> {
+        if max_period == 0 {
+            return None;
+        }
+        // most recent generation first
+        let mut recent = VecDeque::with_capacity(max_period);
+        recent.push_front(self.grid);
+        for _ in 0..max_generations {
+            self.update();
+            if let Some(index) = recent.iter().position(|g| g == &self.grid) {
+                return Some(index + 1);
+            }
+            if recent.len() == max_period {
+                recent.pop_back();
+            }
+            recent.push_front(self.grid);
+        }
+        None
+    }
+
+    /// Run a number of updates and return a hash combining the
+    /// fingerprints of all generations (including the current one).
+    /// Identical runs result in identical hashes.
+    ///
+    /// # Arguments
+    /// * `steps`: number of updates to run
+    pub fn run_hash(&mut self, steps: u32) -> u64 {
+        let mut hash = fnv1a(FNV_OFFSET_BASIS, &self.grid.fingerprint().to_le_bytes());
+        for _ in 0..steps {
+            self.update();
+            hash = fnv1a(hash, &self.grid.fingerprint().to_le_bytes());
+        }
+        hash
+    }
+
+    /// Encode the cells which differ from a reference grid, e.g.
+    /// to send incremental updates over a network. The delta can be
+    /// applied to the reference grid via `Grid::apply_delta()`.
+    ///
+    /// # Arguments
+    /// * `since`: the reference grid (of equal dimensions)
+    #[cfg(feature = "alloc")]
+    pub fn serialize_delta(&self, since: &Grid) -> Vec<u8> {
+        if since.horizontal_size != self.grid.horizontal_size
+            || since.vertical_size != self.grid.vertical_size
+        {
+            panic!("grid dimensions do not match")
+        }
+        let mut delta = Vec::new();
+        for v in 0..self.grid.vertical_size {
+            for h in 0..self.grid.horizontal_size {
+                let state = self.grid.get_cellstate(h, v);
+                if state != since.get_cellstate(h, v) {
+                    delta.extend_from_slice(&[h, v, state.to_byte()]);
+                }
+            }
+        }
+        delta
+    }
+
+    /// Run a number of updates and collect every generation,
+    /// starting with the current one (i.e. `steps + 1` grids).
+    ///
+    /// # Arguments
+    /// * `steps`: number of updates to run
+    #[cfg(feature = "alloc")]
+    pub fn record_frames(&mut self, steps: u32) -> Vec<Grid> {
+        let mut frames = Vec::with_capacity(steps as usize + 1);
+        frames.push(self.grid);
+        for _ in 0..steps {
+            self.update();
+            frames.push(self.grid);
+        }
+        frames
+    }
+
+    /// Update the universe (see `update()`) and hand the new
+    /// generation to a callback, e.g. for visualisation or logging.
+    ///
+    /// # Arguments
+    /// * `callback`: called with the grid and the generation number
+    ///
+    /// # Remarks
+    /// The callback is also called while the universe is paused,
+    /// the generation number does not change then.
+    pub fn update_with<F: FnMut(&Grid, u64)>(&mut self, callback: &mut F) {
+        self.update();
+        callback(&self.grid, self.generation);
+    }
+
+    /// Update the universe (see `update()`) and measure how long
+    /// it took, e.g. to identify slow rules.
+    #[cfg(feature = "std")]
+    pub fn update_timed(&mut self) -> core::time::Duration {
+        let start = std::time::Instant::now();
+        self.update();
+        start.elapsed()
+    }
+
+    /// Append the current population to the history,
+    /// dropping the oldest entry if it is full.
+    #[cfg(feature = "history")]
+    fn record_population(&mut self) {
+        if self.history_len == HISTORY_CAPACITY {
+            self.history.copy_within(1.., 0);
+            self.history_len -= 1;
+        }
+        self.history[self.history_len] = self.grid.population();
+        self.history_len += 1;
+    }
+
+    /// Get the population after each of the last (up to
+    /// `HISTORY_CAPACITY`) updates, the oldest first.
+    #[cfg(feature = "history")]
+    pub fn population_history(&self) -> &[u32] {
+        &self.history[..self.history_len]
+    }
+
+    /// Update the age of a cell before its new state is stored.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    /// * `new`: the state the cell is going to have
+    #[cfg(feature = "age-tracking")]
+    fn track_age(&mut self, h: u8, v: u8, new: CellState) {
+        let old = *self.grid.get_cellstate(h, v);
+        let age = &mut self.ages[h as usize][v as usize];
+        *age = match (old, new) {
+            (CellState::Alive, CellState::Alive) => age.saturating_add(1),
+            _ => 0,
+        };
+    }
+
+    /// Get the number of updates a living cell has survived
+    /// (0 for newborn and dead cells). The age saturates at
+    /// `u16::MAX`.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    #[cfg(feature = "age-tracking")]
+    pub fn cell_age(&self, h: u8, v: u8) -> u16 {
+        if self.grid.get_cellstate(h, v) == &CellState::Dead {
+            return 0;
+        }
+        self.ages[h as usize][v as usize]
+    }
+
+    /// Render the grid into RGB bytes (row by row, three bytes per
+    /// cell) colouring living cells by age: newborn cells are bright
+    /// yellow, fading to dim red at `max_age`. Dead cells are black.
+    ///
+    /// # Arguments
+    /// * `max_age`: the age at which cells reach the final colour
+    #[cfg(all(feature = "alloc", feature = "age-tracking"))]
+    pub fn age_rgb_buffer(&self, max_age: u32) -> Vec<u8> {
+        let mut buffer = Vec::with_capacity(
+            self.grid.horizontal_size as usize * self.grid.vertical_size as usize * 3,
+        );
+        for v in 0..self.grid.vertical_size {
+            for h in 0..self.grid.horizontal_size {
+                if self.grid.get_cellstate(h, v) == &CellState::Dead {
+                    buffer.extend_from_slice(&[0, 0, 0]);
+                    continue;
+                }
+                let age = (self.cell_age(h, v) as u32).min(max_age);
+                let fade = (age * 255).checked_div(max_age).unwrap_or(255);
+                buffer.extend_from_slice(&[(255 - fade * 191 / 255) as u8, (255 - fade) as u8, 0]);
+            }
+        }
+        buffer
+    }
+
+    /// Update the universe by applying a purely cell-local
+    /// transformation in place, skipping the shadow grid.
+    /// This counts as an update like `update()`, i.e. it
+    /// advances the generation and is recorded in the history.
+    ///
+    /// # Arguments
+    /// * `f`: a function mapping the state of a cell to its new state
+    ///
+    /// # Remarks
+    /// Cells are modified one after another. This must NOT be
+    /// used for rules depending on neighbouring cells, since
+    /// those would see a mix of old and new states.
+    pub fn update_local<F: Fn(CellState) -> CellState>(&mut self, f: F) {
+        if self.paused {
+            return;
+        }
+        self.capture_initial();
+        for h in 0..self.grid.horizontal_size {
+            for v in 0..self.grid.vertical_size {
+                if !self.grid.is_active(h, v) {
+                    continue;
+                }
+                let state = f(*self.grid.get_cellstate(h, v));
+                #[cfg(feature = "age-tracking")]
+                self.track_age(h, v, state);
+                self.grid.set_cellstate(h, v, state);
+            }
+        }
+        self.finish_step();
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<C: Sync, R: Fn(u8, u8, &Grid) -> CellState + Sync> Universe<C, R> {
+    /// Update the universe like `update()` (including pausing,
+    /// generation counting and tracking), but calculate the rows
+    /// of the new state in parallel (using rayon).
+    ///
+    /// # Remarks
+    /// The rules have to be independent of the order in which
+    /// cells are calculated (as for `update()`). This is a method
+    /// of its own since rules and context have to be shareable
+    /// between threads (`Sync`): `update()` accepts any rules, so
+    /// enabling the feature does not break existing universes.
+    pub fn update_parallel(&mut self) {
+        use rayon::prelude::*;
+        if self.paused {
+            return;
+        }
+        let h_size = self.grid.horizontal_size;
+        let this = &*self;
+        let rows: Vec<[CellState; HORIZONTAL_MAX]> = (0..self.grid.vertical_size)
+            .into_par_iter()
+            .map(|v| {
+                let mut row = [CellState::default(); HORIZONTAL_MAX];
+                for h in 0..h_size {
+                    row[h as usize] = this.next_state(h, v);
+                }
+                row
+            })
+            .collect();
+        for (v, row) in rows.iter().enumerate() {
+            for h in 0..h_size {
+                self.store_state(h, v as u8, row[h as usize]);
+            }
+        }
+        self.publish_shadow();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "dead-alive-only")]
+    use crate::test_rules::{conway, rule30};
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    use alloc::vec;
+
+    #[test]
+    // check grid creation values
+    fn grid_new() {
+        let g = Grid::new(5, 23);
+        assert_eq!(g.horizontal_size, 5);
+        assert_eq!(g.vertical_size, 23);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_new_too_small() {
+        let _ = Grid::new(0, 1);
+        let _ = Grid::new(1, 0);
+    }
+
+    #[test]
+    // check grid creation values
+    fn grid_get_cellstate() {
+        let g = Grid::new(3, 17);
+        let mut c = g.get_cellstate(1, 8);
+        #[cfg(not(any(
+            feature = "dead-alive-only",
+            feature = "multistate",
+            feature = "wireworld"
+        )))]
+        assert_eq!(c, &CellState::Dummy);
+        #[cfg(feature = "multistate")]
+        assert_eq!(c, &CellState(0));
+        #[cfg(feature = "wireworld")]
+        assert_eq!(c, &CellState::Empty);
+        #[cfg(feature = "dead-alive-only")]
+        assert_eq!(c, &CellState::Dead);
+
+        // test using tuple
+        c = g.get_cellstate_hv((1, 2));
+        #[cfg(not(any(
+            feature = "dead-alive-only",
+            feature = "multistate",
+            feature = "wireworld"
+        )))]
+        assert_eq!(c, &CellState::Dummy);
+        #[cfg(feature = "multistate")]
+        assert_eq!(c, &CellState(0));
+        #[cfg(feature = "wireworld")]
+        assert_eq!(c, &CellState::Empty);
+        #[cfg(feature = "dead-alive-only")]
+        assert_eq!(c, &CellState::Dead);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_cell_v_too_large() {
+        let g = Grid::new(3, 17);
+        let _c = g.get_cellstate(1, 17);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_cell_h_too_large() {
+        let g = Grid::new(3, 1);
+        let _c = g.get_cellstate(3, 0);
+    }
+
+    #[test]
+    #[cfg(any(
+        feature = "dead-alive-only",
+        feature = "multistate",
+        feature = "wireworld"
+    ))]
+    // check grid creation values
+    fn grid_set_cellstate() {
+        let mut g = Grid::new(3, 17);
+        #[cfg(feature = "dead-alive-only")]
+        g.set_cellstate(1, 8, CellState::Alive);
+        #[cfg(feature = "multistate")]
+        g.set_cellstate(1, 8, CellState(3));
+        #[cfg(feature = "wireworld")]
+        g.set_cellstate(1, 8, CellState::Conductor);
+        let mut c = g.get_cellstate(1, 8);
+        #[cfg(feature = "dead-alive-only")]
+        assert_eq!(c, &CellState::Alive);
+        #[cfg(feature = "multistate")]
+        assert_eq!(c, &CellState(3));
+        #[cfg(feature = "wireworld")]
+        assert_eq!(c, &CellState::Conductor);
+
+        // use tuple
+        #[cfg(feature = "dead-alive-only")]
+        g.set_cellstate_hv((2, 5), CellState::Alive);
+        #[cfg(feature = "multistate")]
+        g.set_cellstate_hv((2, 5), CellState(3));
+        #[cfg(feature = "wireworld")]
+        g.set_cellstate_hv((2, 5), CellState::Conductor);
+        c = g.get_cellstate(2, 5);
+        #[cfg(feature = "dead-alive-only")]
+        assert_eq!(c, &CellState::Alive);
+        #[cfg(feature = "multistate")]
+        assert_eq!(c, &CellState(3));
+        #[cfg(feature = "wireworld")]
+        assert_eq!(c, &CellState::Conductor);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_set_cell_v_too_large() {
+        let mut g = Grid::new(3, 17);
+        #[cfg(not(feature = "dead-alive-only"))]
+        g.set_cellstate(1, 17, CellState::default());
+        #[cfg(feature = "dead-alive-only")]
+        g.set_cellstate(1, 17, CellState::Alive);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_set_cell_h_too_large() {
+        let mut g = Grid::new(3, 1);
+        #[cfg(not(feature = "dead-alive-only"))]
+        g.set_cellstate(3, 0, CellState::default());
+        #[cfg(feature = "dead-alive-only")]
+        g.set_cellstate(3, 0, CellState::Alive);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_get_set_coordinate() {
+        let mut g = Grid::new(3, 4);
+        let c = Coordinate { h: 2, v: 3 };
+        g.set(c, CellState::Alive);
+        assert_eq!(g.get(c), &CellState::Alive);
+        assert_eq!(g.get_cellstate(2, 3), &CellState::Alive);
+        assert_eq!(g.get(Coordinate::from((1, 3))), &CellState::Dead);
+        assert_eq!(<(u8, u8)>::from(c), (2, 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_coordinate_h_too_large() {
+        let g = Grid::new(3, 4);
+        let _ = g.get(Coordinate { h: 3, v: 0 });
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_coordinate_v_too_large() {
+        let g = Grid::new(3, 4);
+        let _ = g.get(Coordinate { h: 0, v: 4 });
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_set_coordinate_h_too_large() {
+        let mut g = Grid::new(3, 4);
+        g.set(Coordinate { h: 3, v: 0 }, CellState::default());
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_set_coordinate_v_too_large() {
+        let mut g = Grid::new(3, 4);
+        g.set(Coordinate { h: 0, v: 4 }, CellState::default());
+    }
+
+    #[test]
+    fn grid_get_north_coordinate() {
+        let g = Grid::new(3, 4);
+        let mut result = g.get_north_coordinate(1, 2);
+        assert_eq!(result.0, 1);
+        assert_eq!(result.1, 1);
+
+        result = g.get_north_coordinate(2, 0);
+        assert_eq!(result.0, 2);
+        assert_eq!(result.1, 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_north_coordinate_v_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_north_coordinate(0, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_north_coordinate_h_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_north_coordinate(1, 2);
+    }
+
+    #[test]
+    fn grid_get_south_coordinate() {
+        let g = Grid::new(3, 4);
+        let mut result = g.get_south_coordinate(1, 2);
+        assert_eq!(result.0, 1);
+        assert_eq!(result.1, 3);
+
+        result = g.get_south_coordinate(2, 0);
+        assert_eq!(result.0, 2);
+        assert_eq!(result.1, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_south_coordinate_v_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_south_coordinate(0, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_south_coordinate_h_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_south_coordinate(1, 2);
+    }
+
+    #[test]
+    fn grid_get_west_coordinate() {
+        let g = Grid::new(3, 4);
+        let mut result = g.get_west_coordinate(1, 2);
+        assert_eq!(result.0, 0);
+        assert_eq!(result.1, 2);
+
+        result = g.get_west_coordinate(0, 2);
+        assert_eq!(result.0, 2);
+        assert_eq!(result.1, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_west_coordinate_v_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_west_coordinate(0, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_west_coordinate_h_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_west_coordinate(1, 2);
+    }
+
+    #[test]
+    fn grid_get_northeast_coordinate() {
+        let g = Grid::new(3, 4);
+        let mut result = g.get_northeast_coordinate(1, 2);
+        assert_eq!(result.0, 2);
+        assert_eq!(result.1, 1);
+
+        result = g.get_northeast_coordinate(2, 0);
+        assert_eq!(result.0, 0);
+        assert_eq!(result.1, 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_northeast_coordinate_v_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_northeast_coordinate(0, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_northeast_coordinate_h_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_northeast_coordinate(1, 2);
+    }
+
+    #[test]
+    fn grid_get_southeast_coordinate() {
+        let g = Grid::new(3, 4);
+        let mut result = g.get_southeast_coordinate(1, 2);
+        assert_eq!(result.0, 2);
+        assert_eq!(result.1, 3);
+
+        result = g.get_southeast_coordinate(2, 0);
+        assert_eq!(result.0, 0);
+        assert_eq!(result.1, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_southeast_coordinate_v_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_southeast_coordinate(0, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_southeast_coordinate_h_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_southeast_coordinate(1, 2);
+    }
+
+    #[test]
+    fn grid_get_southwest_coordinate() {
+        let g = Grid::new(3, 4);
+        let mut result = g.get_southwest_coordinate(1, 2);
+        assert_eq!(result.0, 0);
+        assert_eq!(result.1, 3);
+
+        result = g.get_southwest_coordinate(0, 0);
+        assert_eq!(result.0, 2);
+        assert_eq!(result.1, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_southwest_coordinate_v_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_southwest_coordinate(0, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_southwest_coordinate_h_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_southwest_coordinate(1, 2);
+    }
+
+    #[test]
+    fn grid_get_northwest_coordinate() {
+        let g = Grid::new(3, 4);
+        let mut result = g.get_northwest_coordinate(1, 2);
+        assert_eq!(result.0, 0);
+        assert_eq!(result.1, 1);
+
+        result = g.get_northwest_coordinate(0, 0);
+        assert_eq!(result.0, 2);
+        assert_eq!(result.1, 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_northwest_coordinate_v_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_northwest_coordinate(0, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_northwest_coordinate_h_too_large() {
+        let g = Grid::new(1, 4);
+        let _ = g.get_northwest_coordinate(1, 2);
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn grid_set_active_mask() {
+        let mut g = Grid::new(3, 2);
+        g.set_cellstate(0, 0, CellState::Alive);
+        g.set_cellstate(1, 0, CellState::Alive);
+        g.set_active_mask(&[true, false, true, true, true, false]);
+        assert!(g.is_active(0, 0));
+        assert!(!g.is_active(1, 0));
+        assert!(!g.is_active(2, 1));
+        // masked cells are reset
+        assert_eq!(g.get_cellstate(0, 0), &CellState::Alive);
+        assert_eq!(g.get_cellstate(1, 0), &CellState::Dead);
+
+        g.set_active_mask(&[true; 6]);
+        assert!(g.is_active(1, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "alloc")]
+    fn grid_set_active_mask_wrong_size() {
+        let mut g = Grid::new(3, 2);
+        g.set_active_mask(&[true; 5]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn grid_active_mask_transform() {
+        // only (1, 0) is inactive
+        let mut mask = [true; 9];
+        mask[1] = false;
+        let mut g = Grid::new(3, 3);
+        g.set_active_mask(&mask);
+
+        g.flip_horizontal();
+        assert!(g.is_active(0, 0));
+        assert!(!g.is_active(1, 0));
+        g.flip_vertical();
+        assert!(g.is_active(1, 0));
+        assert!(!g.is_active(1, 2));
+        g.rotate_cw().unwrap();
+        assert!(g.is_active(1, 2));
+        assert!(!g.is_active(0, 1));
+        g.roll(1, -2);
+        assert!(g.is_active(0, 1));
+        assert!(!g.is_active(1, 2));
+        let inactive = (0..3u8)
+            .flat_map(|v| (0..3u8).map(move |h| (h, v)))
+            .filter(|&(h, v)| !g.is_active(h, v))
+            .count();
+        assert_eq!(inactive, 1);
+    }
+
+    #[test]
+    fn grid_toroidal_manhattan_distance() {
+        let g = Grid::new(10, 10);
+        assert_eq!(g.toroidal_manhattan_distance((1, 1), (9, 9)), 4);
+        assert_eq!(g.toroidal_manhattan_distance((9, 9), (1, 1)), 4);
+        assert_eq!(g.toroidal_manhattan_distance((2, 3), (4, 6)), 5);
+        assert_eq!(g.toroidal_manhattan_distance((0, 0), (5, 5)), 10);
+        assert_eq!(g.toroidal_manhattan_distance((7, 7), (7, 7)), 0);
+    }
+
+    #[test]
+    fn grid_toroidal_distance() {
+        let g = Grid::new(20, 9);
+        // near opposite edges the way around is shorter
+        assert_eq!(g.toroidal_distance((0, 0), (19, 8)), (1, 1));
+        assert_eq!(g.toroidal_distance((18, 1), (2, 7)), (4, 3));
+        assert_eq!(g.toroidal_distance((2, 7), (18, 1)), (4, 3));
+        assert_eq!(g.toroidal_distance((3, 2), (8, 4)), (5, 2));
+        // at most half the size
+        assert_eq!(g.toroidal_distance((0, 0), (10, 4)), (10, 4));
+        assert_eq!(g.toroidal_distance((0, 0), (11, 5)), (9, 4));
+        assert_eq!(g.toroidal_distance((5, 5), (5, 5)), (0, 0));
+
+        let max = Grid::new(255, 255);
+        assert_eq!(max.toroidal_distance((0, 0), (254, 127)), (1, 127));
+        assert_eq!(max.toroidal_distance((0, 0), (128, 200)), (127, 55));
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_toroidal_distance_too_large() {
+        let g = Grid::new(10, 10);
+        let _ = g.toroidal_distance((10, 1), (1, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_toroidal_manhattan_distance_too_large() {
+        let g = Grid::new(10, 10);
+        let _ = g.toroidal_manhattan_distance((1, 1), (10, 9));
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_any_all_moore_alive() {
+        let mut g = Grid::new(5, 5);
+        // isolated cell
+        g.set_cellstate(2, 2, CellState::Alive);
+        assert!(!g.any_moore_alive(2, 2));
+        assert!(!g.all_moore_alive(2, 2));
+
+        // partially surrounded
+        g.set_cellstate(1, 1, CellState::Alive);
+        assert!(g.any_moore_alive(2, 2));
+        assert!(!g.all_moore_alive(2, 2));
+
+        // fully surrounded
+        for h in 1..4u8 {
+            for v in 1..4u8 {
+                g.set_cellstate(h, v, CellState::Alive);
+            }
+        }
+        assert!(g.any_moore_alive(2, 2));
+        assert!(g.all_moore_alive(2, 2));
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_clear() {
+        let mut g = Grid::new(4, 3);
+        g.set_cellstate(0, 0, CellState::Alive);
+        g.set_cellstate(3, 2, CellState::Alive);
+        g.set_cellstate(1, 1, CellState::Alive);
+        g.clear();
+        for h in 0..4 {
+            for v in 0..3 {
+                assert_eq!(g.get_cellstate(h, v), &CellState::Dead);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_fill() {
+        let mut g = Grid::new(5, 3);
+        g.fill(CellState::Alive);
+        for hv in [(0, 0), (4, 0), (0, 2), (4, 2), (2, 1)] {
+            assert_eq!(g.get_cellstate_hv(hv), &CellState::Alive);
+        }
+        // the backing array beyond the grid is untouched
+        #[cfg(not(feature = "packed"))]
+        {
+            assert_eq!(g.cells[5][0], CellState::Dead);
+            assert_eq!(g.cells[0][3], CellState::Dead);
+        }
+        #[cfg(feature = "packed")]
+        {
+            assert_eq!(g.cells[0][0], 0b1111_1000);
+            assert_eq!(g.cells[3][0], 0);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "packed")]
+    fn grid_count_live_neighbours_row() {
+        let sizes = [(255, 9), (70, 5), (64, 3), (1, 1), (3, 2)];
+        for (i, &(h_size, v_size)) in sizes.iter().enumerate() {
+            for topology in [Topology::Toroidal, Topology::Bounded] {
+                let mut g = Grid::with_topology(h_size, v_size, topology);
+                g.randomize(i as u64 + 1, 100);
+                for v in 0..v_size {
+                    let counts = g.count_live_neighbours_row(v);
+                    for h in 0..h_size {
+                        assert_eq!(counts[h as usize], g.count_live_neighbours(h, v));
+                    }
+                    assert!(counts[h_size as usize..].iter().all(|c| *c == 0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "packed")]
+    fn grid_packed_storage() {
+        let pattern = |h: u8, v: u8| (h as u16 * 7 + v as u16 * 13) % 5 == 0;
+        let mut g = Grid::new(255, 255);
+        for v in 0..255 {
+            for h in 0..255 {
+                if pattern(h, v) {
+                    g.set_cellstate(h, v, CellState::Alive);
+                }
+            }
+        }
+        // kill a cell, its neighbours within the same byte survive
+        g.set_cellstate(5, 0, CellState::Dead);
+        g.set_cellstate(0, 0, CellState::Alive);
+        for v in 0..255 {
+            for h in 0..255 {
+                let expected = match (h, v) {
+                    (5, 0) => CellState::Dead,
+                    (0, 0) => CellState::Alive,
+                    _ if pattern(h, v) => CellState::Alive,
+                    _ => CellState::Dead,
+                };
+                assert_eq!(g.get_cellstate(h, v), &expected);
+            }
+        }
+
+        // one bit per cell (rows are rounded up to full bytes)
+        let unpacked = core::mem::size_of::<[[CellState; HORIZONTAL_MAX]; VERTICAL_MAX]>();
+        let packed = core::mem::size_of::<CellArray>();
+        assert_eq!(packed, HORIZONTAL_BYTES * VERTICAL_MAX);
+        assert!(packed * 8 >= unpacked);
+        assert!(packed * 8 < unpacked + 8 * VERTICAL_MAX);
+
+        // the whole grid shrinks, apart from the active mask (same size as the cells)
+        let mask = if cfg!(feature = "alloc") { packed } else { 0 };
+        let grid = core::mem::size_of::<Grid>();
+        assert!((grid - mask) * 7 < unpacked);
+        assert!(grid * 3 < unpacked);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_iter_cells() {
+        let mut g = Grid::new(5, 3);
+        g.set_cellstate(4, 1, CellState::Alive);
+        assert_eq!(g.iter_cells().count(), 5 * 3);
+        assert_eq!(g.iter_cells().next(), Some((0, 0, &CellState::Dead)));
+        assert_eq!(g.iter_cells().nth(6), Some((1, 1, &CellState::Dead)));
+        assert_eq!(g.iter_cells().last(), Some((4, 2, &CellState::Dead)));
+        let alive: u32 = g
+            .iter_cells()
+            .filter(|(_, _, cs)| **cs == CellState::Alive)
+            .map(|(h, v, _)| {
+                assert_eq!((h, v), (4, 1));
+                1
+            })
+            .sum();
+        assert_eq!(alive, g.population());
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_index() {
+        let mut g = Grid::new(4, 3);
+        g.set_cellstate(1, 0, CellState::Alive);
+        assert_eq!(g[(1, 0)], CellState::Alive);
+        assert_eq!(g[(0, 0)], CellState::Dead);
+        assert_eq!(g[(1, 0)], *g.get_cellstate(1, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_index_out_of_bounds() {
+        let g = Grid::new(4, 3);
+        let _ = g[(4, 0)];
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_partial_eq() {
+        let mut a = Grid::new(4, 3);
+        let mut b = Grid::new(4, 3);
+        for hv in [(0, 0), (2, 1), (3, 2)] {
+            a.set_cellstate_hv(hv, CellState::Alive);
+            b.set_cellstate_hv(hv, CellState::Alive);
+        }
+        assert_eq!(a, b);
+        b.set_cellstate(1, 1, CellState::Alive);
+        assert_ne!(a, b);
+
+        // dimensions matter, the unused backing array does not
+        assert_ne!(Grid::new(4, 3), Grid::new(3, 4));
+        let mut c = Grid::new(2, 2);
+        #[cfg(not(feature = "packed"))]
+        {
+            c.cells[3][3] = CellState::Alive;
+        }
+        #[cfg(feature = "packed")]
+        {
+            c.cells[3][0] = 0b0001_0000;
+        }
+        assert_eq!(c, Grid::new(2, 2));
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "dead-alive-only"))]
+    fn grid_hash() {
+        use std::collections::HashSet;
+
+        let mut a = Grid::new(4, 3);
+        let mut b = Grid::with_topology(4, 3, Topology::Bounded);
+        a.set_cellstate(2, 1, CellState::Alive);
+        b.set_cellstate(2, 1, CellState::Alive);
+        // the unused backing array is ignored
+        #[cfg(not(feature = "packed"))]
+        {
+            b.cells[3][3] = CellState::Alive;
+        }
+
+        let mut set = HashSet::new();
+        set.insert(a);
+        set.insert(b);
+        assert_eq!(set.len(), 1);
+        set.insert(Grid::new(4, 3));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn grid_try_new() {
+        assert_eq!(
+            Grid::try_new(0, 1).unwrap_err(),
+            GridError::InvalidDimensions
+        );
+        assert_eq!(
+            Grid::try_new(1, 0).unwrap_err(),
+            GridError::InvalidDimensions
+        );
+        assert_eq!(
+            Grid::try_new(0, 0).unwrap_err(),
+            GridError::InvalidDimensions
+        );
+
+        let g = Grid::try_new(u8::MAX, u8::MAX).unwrap();
+        assert_eq!(g.get_horizontal_size() as usize, HORIZONTAL_MAX);
+        assert_eq!(g.get_vertical_size() as usize, VERTICAL_MAX);
+        let g = Grid::try_new(1, 1).unwrap();
+        assert_eq!(g.get_horizontal_size(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_try_get_set_cellstate() {
+        let mut g = Grid::new(3, 2);
+        assert_eq!(g.try_set_cellstate(2, 1, CellState::Alive), Ok(()));
+        assert_eq!(g.try_get_cellstate(2, 1), Ok(&CellState::Alive));
+        assert_eq!(g.try_get_cellstate(0, 0), Ok(&CellState::Dead));
+
+        assert_eq!(
+            g.try_get_cellstate(3, 0),
+            Err(GridError::HorizontalOutOfBounds)
+        );
+        assert_eq!(
+            g.try_get_cellstate(0, 2),
+            Err(GridError::VerticalOutOfBounds)
+        );
+        assert_eq!(
+            g.try_set_cellstate(3, 2, CellState::Alive),
+            Err(GridError::HorizontalOutOfBounds)
+        );
+        assert_eq!(
+            g.try_set_cellstate(1, 2, CellState::Alive),
+            Err(GridError::VerticalOutOfBounds)
+        );
+        // nothing changed
+        assert_eq!(g.population(), 1);
+    }
+
+    #[test]
+    fn grid_get_moore_neighbourhood() {
+        let g = Grid::new(4, 4);
+        for (h, v) in [(1, 1), (2, 2), (0, 0), (3, 0), (0, 3), (3, 2)] {
+            let n = g.get_moore_neighbourhood(h, v);
+            assert_eq!(n[0], g.get_north_coordinate(h, v));
+            assert_eq!(n[1], g.get_northeast_coordinate(h, v));
+            assert_eq!(n[2], g.get_east_coordinate(h, v));
+            assert_eq!(n[3], g.get_southeast_coordinate(h, v));
+            assert_eq!(n[4], g.get_south_coordinate(h, v));
+            assert_eq!(n[5], g.get_southwest_coordinate(h, v));
+            assert_eq!(n[6], g.get_west_coordinate(h, v));
+            assert_eq!(n[7], g.get_northwest_coordinate(h, v));
+        }
+        assert_eq!(
+            g.get_moore_neighbourhood(0, 0),
+            [
+                (0, 3),
+                (1, 3),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (3, 1),
+                (3, 0),
+                (3, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn grid_axis_boundaries() {
+        let g = Grid::with_axis_boundaries(4, 3, BoundaryMode::Wrap, BoundaryMode::Fixed);
+        assert_eq!(g.get_horizontal_boundary(), BoundaryMode::Wrap);
+        assert_eq!(g.get_vertical_boundary(), BoundaryMode::Fixed);
+        // horizontal axis wraps around
+        assert_eq!(g.get_east_coordinate(3, 1), (0, 1));
+        assert_eq!(g.get_west_coordinate(0, 1), (3, 1));
+        assert_eq!(g.neighbour(0, 1, Direction::West), Some((3, 1)));
+        // vertical axis ends at the edges
+        assert_eq!(g.neighbour(2, 0, Direction::North), None);
+        assert_eq!(g.neighbour(0, 0, Direction::NorthWest), None);
+        assert_eq!(g.neighbour(2, 2, Direction::South), None);
+        assert_eq!(g.neighbour(2, 1, Direction::North), Some((2, 0)));
+        assert_eq!(g.neighbour(0, 1, Direction::SouthWest), Some((3, 2)));
+
+        // default is a torus
+        let g = Grid::new(4, 3);
+        assert_eq!(g.get_horizontal_boundary(), BoundaryMode::Wrap);
+        assert_eq!(g.neighbour(2, 0, Direction::North), Some((2, 2)));
+    }
+
+    #[test]
+    fn grid_with_topology() {
+        let g = Grid::with_topology(4, 4, Topology::Bounded);
+        let valid = |h, v| {
+            Direction::ALL
+                .iter()
+                .filter(|d| g.neighbour(h, v, **d).is_some())
+                .count()
+        };
+        assert_eq!(valid(0, 0), 3);
+        assert_eq!(valid(3, 3), 3);
+        assert_eq!(valid(0, 2), 5);
+        assert_eq!(valid(1, 2), 8);
+        assert_eq!(g.neighbour(0, 0, Direction::East), Some((1, 0)));
+        assert_eq!(g.neighbour(0, 0, Direction::West), None);
+
+        let g = Grid::with_topology(4, 4, Topology::Toroidal);
+        assert_eq!(g.get_horizontal_boundary(), BoundaryMode::Wrap);
+        assert_eq!(g.get_vertical_boundary(), BoundaryMode::Wrap);
+        assert_eq!(g.neighbour(0, 0, Direction::NorthWest), Some((3, 3)));
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_with_topology_count() {
+        let mut g = Grid::with_topology(3, 3, Topology::Bounded);
+        for h in 0..3 {
+            for v in 0..3 {
+                g.set_cellstate(h, v, CellState::Alive);
+            }
+        }
+        assert_eq!(g.count_live_neighbours(0, 0), 3);
+        assert_eq!(g.count_live_neighbours(1, 0), 5);
+        assert_eq!(g.count_live_neighbours(1, 1), 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_axis_boundaries_fixed_coordinate() {
+        let g = Grid::with_axis_boundaries(4, 3, BoundaryMode::Wrap, BoundaryMode::Fixed);
+        let _ = g.get_north_coordinate(2, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_axis_boundaries_moore() {
+        let mut g = Grid::with_axis_boundaries(3, 3, BoundaryMode::Wrap, BoundaryMode::Fixed);
+        for h in 0..3 {
+            for v in 0..3 {
+                g.set_cellstate(h, v, CellState::Alive);
+            }
+        }
+        assert!(g.all_moore_alive(1, 1));
+        assert!(!g.all_moore_alive(1, 0));
+        assert_eq!(g.count_alive_moore_inclusive(1, 0), 6);
+        assert_eq!(g.count_alive_moore_inclusive(1, 1), 9);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_count_live_neighbours() {
+        let mut g = Grid::new(3, 3);
+        for h in 0..3 {
+            for v in 0..3 {
+                g.set_cellstate(h, v, CellState::Alive);
+            }
+        }
+        assert_eq!(g.count_live_neighbours(1, 1), 8);
+
+        // wrap around the edges
+        let mut g = Grid::new(5, 5);
+        g.set_cellstate(4, 4, CellState::Alive);
+        g.set_cellstate(0, 4, CellState::Alive);
+        g.set_cellstate(4, 0, CellState::Alive);
+        assert_eq!(g.count_live_neighbours(0, 0), 3);
+        assert_eq!(g.count_live_neighbours(2, 2), 0);
+
+        // aliasing neighbours are counted individually
+        let mut g = Grid::new(1, 1);
+        g.set_cellstate(0, 0, CellState::Alive);
+        assert_eq!(g.count_live_neighbours(0, 0), 8);
+        let mut g = Grid::new(1, 3);
+        g.set_cellstate(0, 0, CellState::Alive);
+        // N, NE, NW alias (0, 0) and E, W alias (0, 1)
+        assert_eq!(g.count_live_neighbours(0, 1), 3);
+        assert_eq!(g.count_live_neighbours(0, 0), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_count_alive_moore_inclusive() {
+        let mut g = Grid::new(5, 5);
+        g.set_cellstate(2, 2, CellState::Alive);
+        g.set_cellstate(1, 1, CellState::Alive);
+        g.set_cellstate(3, 2, CellState::Alive);
+        assert_eq!(g.count_alive_moore_inclusive(2, 2), 3);
+        assert_eq!(g.count_live_neighbours(2, 2), 2);
+
+        // dead center
+        assert_eq!(g.count_alive_moore_inclusive(2, 1), 3);
+        assert_eq!(g.count_live_neighbours(2, 1), 3);
+        assert_eq!(g.count_alive_moore_inclusive(4, 4), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_moore_alive_parity() {
+        let mut g = Grid::new(5, 5);
+        assert!(!g.moore_alive_parity(2, 2));
+
+        // the cell itself does not count
+        g.set_cellstate(2, 2, CellState::Alive);
+        g.set_cellstate(1, 1, CellState::Alive);
+        g.set_cellstate(3, 1, CellState::Alive);
+        assert!(!g.moore_alive_parity(2, 2));
+
+        g.set_cellstate(2, 3, CellState::Alive);
+        assert!(g.moore_alive_parity(2, 2));
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_retain() {
+        let mut g = Grid::new(5, 3);
+        for h in 0..5u8 {
+            for v in 0..3u8 {
+                g.set_cellstate(h, v, CellState::Alive);
+            }
+        }
+        g.retain(|h, _, _| h % 2 == 0);
+        for h in 0..5u8 {
+            for v in 0..3u8 {
+                if h % 2 == 0 {
+                    assert_eq!(g.get_cellstate(h, v), &CellState::Alive);
+                } else {
+                    assert_eq!(g.get_cellstate(h, v), &CellState::Dead);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_map_region() {
+        let invert = |_, _, cs| match cs {
+            CellState::Alive => CellState::Dead,
+            CellState::Dead => CellState::Alive,
+        };
+        let mut g = Grid::new(6, 5);
+        g.set_cellstate(2, 2, CellState::Alive);
+        g.map_region((1, 1), (2, 2), invert);
+        assert_eq!(g.population(), 3);
+        assert_eq!(g.get_cellstate(1, 1), &CellState::Alive);
+        assert_eq!(g.get_cellstate(2, 1), &CellState::Alive);
+        assert_eq!(g.get_cellstate(1, 2), &CellState::Alive);
+        assert_eq!(g.get_cellstate(2, 2), &CellState::Dead);
+
+        // wrap around the south eastern corner
+        let mut g = Grid::new(6, 5);
+        g.map_region((5, 4), (2, 2), invert);
+        assert_eq!(g.population(), 4);
+        assert_eq!(g.get_cellstate(5, 4), &CellState::Alive);
+        assert_eq!(g.get_cellstate(0, 4), &CellState::Alive);
+        assert_eq!(g.get_cellstate(5, 0), &CellState::Alive);
+        assert_eq!(g.get_cellstate(0, 0), &CellState::Alive);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_count_live_in_radius() {
+        let mut g = Grid::new(9, 7);
+        g.randomize(3, 120);
+        for h in 0..9 {
+            for v in 0..7 {
+                assert_eq!(
+                    g.count_live_in_radius(h, v, 1),
+                    g.count_live_neighbours(h, v) as u16
+                );
+            }
+        }
+
+        // radius 2 covers 5x5 cells, wrapping around the corner
+        let mut g = Grid::new(12, 10);
+        g.fill_region((10, 8), (2, 2), CellState::Alive);
+        assert_eq!(g.count_live_in_radius(0, 0, 2), 24);
+        assert_eq!(g.count_live_in_radius(6, 5, 2), 0);
+        g.set_cellstate(6, 5, CellState::Alive);
+        g.set_cellstate(8, 7, CellState::Alive);
+        assert_eq!(g.count_live_in_radius(6, 5, 2), 1);
+        assert_eq!(g.count_live_in_radius(6, 5, 1), 0);
+
+        // larger than the grid: every other cell once
+        let mut g = Grid::new(4, 3);
+        g.fill(CellState::Alive);
+        assert_eq!(g.count_live_in_radius(1, 1, 8), 11);
+
+        // nothing beyond fixed boundaries
+        let mut g = Grid::with_topology(9, 7, Topology::Bounded);
+        g.fill(CellState::Alive);
+        assert_eq!(g.count_live_in_radius(0, 0, 2), 8);
+        assert_eq!(g.count_live_in_radius(8, 3, 2), 14);
+    }
+
+    #[test]
+    fn grid_get_hex_neighbourhood() {
+        let g = Grid::new(6, 4);
+        // even row
+        assert_eq!(
+            g.get_hex_neighbourhood(2, 2),
+            [(2, 1), (3, 2), (2, 3), (1, 3), (1, 2), (1, 1)]
+        );
+        // odd row
+        assert_eq!(
+            g.get_hex_neighbourhood(2, 1),
+            [(3, 0), (3, 1), (3, 2), (2, 2), (1, 1), (2, 0)]
+        );
+        // wrapping at the north western and south eastern corners
+        assert_eq!(
+            g.get_hex_neighbourhood(0, 0),
+            [(0, 3), (1, 0), (0, 1), (5, 1), (5, 0), (5, 3)]
+        );
+        assert_eq!(
+            g.get_hex_neighbourhood(5, 3),
+            [(0, 2), (0, 3), (0, 0), (5, 0), (4, 3), (5, 2)]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_get_hex_neighbourhood_bounded() {
+        let g = Grid::with_topology(6, 4, Topology::Bounded);
+        let _ = g.get_hex_neighbourhood(0, 2);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_get_neighbour_states() {
+        let mut g = Grid::new(7, 5);
+        g.randomize(7, 100);
+        for h in 0..7 {
+            for v in 0..5 {
+                let moore = g.get_moore_states(h, v);
+                for (cs, hv) in moore.iter().zip(g.get_moore_neighbourhood(h, v)) {
+                    assert_eq!(cs, g.get_cellstate_hv(hv));
+                }
+                let von_neumann = [
+                    g.get_north_coordinate(h, v),
+                    g.get_east_coordinate(h, v),
+                    g.get_south_coordinate(h, v),
+                    g.get_west_coordinate(h, v),
+                ];
+                for (cs, hv) in g.get_von_neumann_states(h, v).iter().zip(von_neumann) {
+                    assert_eq!(cs, g.get_cellstate_hv(hv));
+                }
+            }
+        }
+
+        // no cells beyond a bounded grid
+        let mut g = Grid::with_topology(3, 3, Topology::Bounded);
+        g.fill(CellState::Alive);
+        let dead = CellState::Dead;
+        let alive = CellState::Alive;
+        assert_eq!(
+            g.get_moore_states(0, 0),
+            [dead, dead, alive, alive, alive, dead, dead, dead]
+        );
+        assert_eq!(g.get_von_neumann_states(2, 2), [alive, dead, dead, alive]);
+    }
+
+    #[test]
+    #[cfg(feature = "multistate")]
+    fn grid_count_neighbours_matching() {
+        let mut g = Grid::new(4, 4);
+        g.set_cellstate(0, 0, CellState(2));
+        g.set_cellstate(1, 0, CellState(2));
+        g.set_cellstate(2, 1, CellState(1));
+        g.set_cellstate(3, 3, CellState(2));
+        assert_eq!(
+            g.count_neighbours_matching(1, 1, |cs| cs == &CellState(2)),
+            2
+        );
+        assert_eq!(
+            g.count_neighbours_matching(1, 1, |cs| cs == &CellState(1)),
+            1
+        );
+        assert_eq!(g.count_neighbours_matching(1, 1, |cs| cs.0 != 0), 3);
+        // the cell itself is not counted, neighbours wrap around
+        assert_eq!(
+            g.count_neighbours_matching(0, 0, |cs| cs == &CellState(2)),
+            2
+        );
+
+        let g = Grid::with_topology(4, 4, Topology::Bounded);
+        assert_eq!(g.count_neighbours_matching(0, 0, |_| true), 3);
+        assert_eq!(g.count_neighbours_matching(1, 1, |_| true), 8);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_blit() {
+        let mut src = Grid::new(4, 4);
+        src.set_cellstate(1, 1, CellState::Alive);
+        src.set_cellstate(2, 2, CellState::Alive);
+
+        let mut dst = Grid::new(8, 6);
+        dst.fill(CellState::Alive);
+        dst.blit(&src, (1, 1), (2, 2), (5, 3));
+        assert_eq!(dst.get_cellstate(5, 3), &CellState::Alive);
+        assert_eq!(dst.get_cellstate(6, 3), &CellState::Dead);
+        assert_eq!(dst.get_cellstate(5, 4), &CellState::Dead);
+        assert_eq!(dst.get_cellstate(6, 4), &CellState::Alive);
+        assert_eq!(dst.population(), 46);
+
+        // wrapping around both grids
+        let mut dst = Grid::new(8, 6);
+        dst.blit(&src, (2, 2), (4, 4), (7, 5));
+        assert_eq!(dst.population(), 2);
+        assert_eq!(dst.get_cellstate(7, 5), &CellState::Alive);
+        assert_eq!(dst.get_cellstate(2, 2), &CellState::Alive);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_fill_region() {
+        let mut g = Grid::new(6, 5);
+        g.fill_region((1, 1), (3, 2), CellState::Alive);
+        assert_eq!(g.population(), 6);
+        for h in 1..=3 {
+            for v in 1..=2 {
+                assert_eq!(g.get_cellstate(h, v), &CellState::Alive);
+            }
+        }
+        g.fill_region((2, 2), (2, 2), CellState::Dead);
+        assert_eq!(g.population(), 5);
+        assert_eq!(g.get_cellstate(2, 2), &CellState::Dead);
+
+        // wrap around the eastern and southern edges
+        let mut g = Grid::new(6, 5);
+        g.fill_region((4, 3), (0, 0), CellState::Alive);
+        assert_eq!(g.population(), 9);
+        for h in [4, 5, 0] {
+            for v in [3, 4, 0] {
+                assert_eq!(g.get_cellstate(h, v), &CellState::Alive);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_fill_region_too_large() {
+        let mut g = Grid::new(6, 5);
+        g.fill_region((0, 0), (6, 0), CellState::default());
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_flip() {
+        // L-shape:
+        // O...
+        // O...
+        // OO..
+        let l_shape = [(0, 0), (0, 1), (0, 2), (1, 2)];
+        let mut g = Grid::new(4, 3);
+        for hv in l_shape {
+            g.set_cellstate_hv(hv, CellState::Alive);
+        }
+
+        g.flip_horizontal();
+        let mirrored = [(3, 0), (3, 1), (3, 2), (2, 2)];
+        for hv in mirrored {
+            assert_eq!(g.get_cellstate_hv(hv), &CellState::Alive);
+        }
+        assert_eq!(g.population(), 4);
+
+        g.flip_vertical();
+        let rotated = [(3, 2), (3, 1), (3, 0), (2, 0)];
+        for hv in rotated {
+            assert_eq!(g.get_cellstate_hv(hv), &CellState::Alive);
+        }
+        assert_eq!(g.population(), 4);
+
+        // flipping twice restores the grid
+        let copy = g;
+        g.flip_vertical();
+        g.flip_vertical();
+        assert!(g == copy);
+        g.flip_horizontal();
+        g.flip_horizontal();
+        assert!(g == copy);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_is_symmetric() {
+        // arrow pointing east:
+        // O..
+        // .O.
+        // O..
+        let mut g = Grid::new(3, 3);
+        g.set_cellstate(0, 0, CellState::Alive);
+        g.set_cellstate(1, 1, CellState::Alive);
+        g.set_cellstate(0, 2, CellState::Alive);
+        assert!(g.is_symmetric(Symmetry::Vertical));
+        assert!(!g.is_symmetric(Symmetry::Horizontal));
+        assert!(!g.is_symmetric(Symmetry::Rotation180));
+
+        // asymmetric
+        g.set_cellstate(2, 2, CellState::Alive);
+        g.set_cellstate(0, 2, CellState::Dead);
+        assert!(!g.is_symmetric(Symmetry::Vertical));
+        assert!(!g.is_symmetric(Symmetry::Horizontal));
+        // diagonal line O.. / .O. / ..O
+        assert!(g.is_symmetric(Symmetry::Rotation180));
+
+        assert!(Grid::new(4, 2).is_symmetric(Symmetry::Horizontal));
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_rotate_cw() {
+        // O..
+        // OO.
+        // ...
+        let mut g = Grid::new(3, 3);
+        for hv in [(0, 0), (0, 1), (1, 1)] {
+            g.set_cellstate_hv(hv, CellState::Alive);
+        }
+        let original = g;
+
+        g.rotate_cw().unwrap();
+        // .OO
+        // .O.
+        // ...
+        for hv in [(2, 0), (1, 0), (1, 1)] {
+            assert_eq!(g.get_cellstate_hv(hv), &CellState::Alive);
+        }
+        assert_eq!(g.population(), 3);
+
+        // four rotations restore the pattern
+        for _ in 0..2 {
+            assert!(g != original);
+            g.rotate_cw().unwrap();
+        }
+        assert!(g != original);
+        g.rotate_cw().unwrap();
+        assert!(g == original);
+
+        let mut g = Grid::new(4, 3);
+        g.set_cellstate(0, 0, CellState::Alive);
+        assert_eq!(g.rotate_cw(), Err(GridError::NotSquare));
+        assert_eq!(g.get_cellstate(0, 0), &CellState::Alive);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_translate() {
+        let mut g = Grid::new(5, 4);
+        g.set_cellstate(4, 0, CellState::Alive);
+        // one cell east and north, wrapping on both axes
+        g.translate(1, -1);
+        assert_eq!(g.get_cellstate(0, 3), &CellState::Alive);
+        assert_eq!(g.population(), 1);
+
+        // more than the grid size
+        g.translate(12, -9);
+        assert_eq!(g.get_cellstate(2, 2), &CellState::Alive);
+        assert_eq!(g.population(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_roll() {
+        let mut g = Grid::new(5, 5);
+        g.set_cellstate(3, 4, CellState::Alive);
+        g.roll(2, 1);
+        assert_eq!(g.get_cellstate(0, 0), &CellState::Alive);
+        assert_eq!(g.get_cellstate(3, 4), &CellState::Dead);
+        assert_eq!(g.population(), 1);
+
+        // negative offsets and more than one revolution
+        g.roll(-11, 5);
+        assert_eq!(g.get_cellstate(4, 0), &CellState::Alive);
+        assert_eq!(g.population(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_fingerprint() {
+        let mut g1 = Grid::new(4, 4);
+        let mut g2 = Grid::new(4, 4);
+        g1.set_cellstate(1, 2, CellState::Alive);
+        g2.set_cellstate(1, 2, CellState::Alive);
+        assert_eq!(g1.fingerprint(), g2.fingerprint());
+
+        g2.set_cellstate(2, 1, CellState::Alive);
+        assert_ne!(g1.fingerprint(), g2.fingerprint());
+
+        // dimensions count as well
+        assert_ne!(Grid::new(2, 8).fingerprint(), Grid::new(8, 2).fingerprint());
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_checksum() {
+        let mut g1 = Grid::new(7, 5);
+        g1.randomize(11, 90);
+        let mut g2 = Grid::new(7, 5);
+        for (h, v, cs) in g1.iter_cells() {
+            g2.set_cellstate(h, v, *cs);
         }
+        assert_eq!(g1.checksum(), g2.checksum());
 
-        // copy over new (shadow) state to public grid
-        //self.grid = self.shadow;
-        for h in 0..self.grid.horizontal_size {
-            for v in 0..self.grid.vertical_size {
-                let state = self.shadow.get_cellstate(h, v);
-                self.grid.set_cellstate(h, v, *state); // does not work
+        // flip every single cell once
+        for h in 0..7 {
+            for v in 0..5 {
+                let flipped = match g1.get_cellstate(h, v) {
+                    CellState::Alive => CellState::Dead,
+                    CellState::Dead => CellState::Alive,
+                };
+                let mut g3 = g1;
+                g3.set_cellstate(h, v, flipped);
+                assert_ne!(g1.checksum(), g3.checksum());
             }
         }
+        // stable value (single dead cell)
+        assert_eq!(Grid::new(1, 1).checksum(), 0xd0a6_fd18_672a_1435);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    // check grid creation values
-    fn grid_new() {
-        let g = Grid::new(5, 23);
-        assert_eq!(g.horizontal_size, 5);
-        assert_eq!(g.vertical_size, 23);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_first_difference() {
+        let mut g1 = Grid::new(4, 3);
+        let mut g2 = Grid::new(4, 3);
+        assert_eq!(g1.first_difference(&g2), None);
+
+        g1.set_cellstate(1, 2, CellState::Alive);
+        g2.set_cellstate(3, 1, CellState::Alive);
+        assert_eq!(g1.first_difference(&g2), Some((3, 1)));
+        assert_eq!(g2.first_difference(&g1), Some((3, 1)));
+
+        g2.set_cellstate(3, 1, CellState::Dead);
+        g2.set_cellstate(1, 2, CellState::Alive);
+        assert_eq!(g1.first_difference(&g2), None);
     }
 
     #[test]
     #[should_panic]
-    fn grid_new_too_small() {
-        let _ = Grid::new(0, 1);
-        let _ = Grid::new(1, 0);
+    fn grid_first_difference_dimensions() {
+        let _ = Grid::new(4, 3).first_difference(&Grid::new(3, 4));
     }
 
     #[test]
-    // check grid creation values
-    fn grid_get_cellstate() {
-        let g = Grid::new(3, 17);
-        let mut c = g.get_cellstate(1, 8);
-        #[cfg(not(feature = "dead-alive-only"))]
-        assert_eq!(c, &CellState::Dummy);
-        #[cfg(feature = "dead-alive-only")]
-        assert_eq!(c, &CellState::Dead);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_apply_delta_errors() {
+        let mut g = Grid::new(3, 3);
+        assert_eq!(g.apply_delta(&[0, 0]), Err(ParseError::Truncated));
+        assert_eq!(g.apply_delta(&[3, 0, 1]), Err(ParseError::OutOfBounds));
+        assert_eq!(
+            g.apply_delta(&[0, 0, 1, 1, 1, 7]),
+            Err(ParseError::InvalidState(7))
+        );
+        // nothing applied on error
+        assert_eq!(g.population(), 0);
 
-        // test using tuple
-        c = g.get_cellstate_hv((1, 2));
-        #[cfg(not(feature = "dead-alive-only"))]
-        assert_eq!(c, &CellState::Dummy);
-        #[cfg(feature = "dead-alive-only")]
-        assert_eq!(c, &CellState::Dead);
+        assert_eq!(g.apply_delta(&[0, 0, 1, 2, 1, 1]), Ok(()));
+        assert_eq!(g.get_cellstate(0, 0), &CellState::Alive);
+        assert_eq!(g.get_cellstate(2, 1), &CellState::Alive);
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_cell_v_too_large() {
-        let g = Grid::new(3, 17);
-        let _c = g.get_cellstate(1, 17);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_resize_preserving_center() {
+        let mut g = Grid::new(3, 3);
+        g.set_cellstate(1, 1, CellState::Alive);
+        g.resize_preserving_center(5, 5).unwrap();
+        assert_eq!(g.get_horizontal_size(), 5);
+        assert_eq!(g.get_vertical_size(), 5);
+        assert_eq!(g.get_cellstate(2, 2), &CellState::Alive);
+        assert_eq!(g.population(), 1);
+
+        // shrinking drops the margins
+        g.set_cellstate(0, 0, CellState::Alive);
+        g.resize_preserving_center(3, 3).unwrap();
+        assert_eq!(g.get_cellstate(1, 1), &CellState::Alive);
+        assert_eq!(g.population(), 1);
+
+        // no stale cells when growing again
+        g.resize_preserving_center(5, 5).unwrap();
+        assert_eq!(g.get_cellstate(0, 0), &CellState::Dead);
+        assert_eq!(g.population(), 1);
+
+        // invalid dimensions change nothing
+        let before = g;
+        assert_eq!(
+            g.resize_preserving_center(0, 5),
+            Err(GridError::InvalidDimensions)
+        );
+        assert_eq!(
+            g.resize_preserving_center(5, 0),
+            Err(GridError::InvalidDimensions)
+        );
+        assert!(g == before);
+        assert_eq!(g.get_horizontal_size(), 5);
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_cell_h_too_large() {
-        let g = Grid::new(3, 1);
-        let _c = g.get_cellstate(3, 0);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_is_extinct() {
+        let mut g = Grid::new(4, 4);
+        assert!(g.is_extinct());
+        g.set_cellstate(3, 3, CellState::Alive);
+        assert!(!g.is_extinct());
+
+        let mut u = Universe::new(4, 4, conway);
+        assert!(u.is_extinct());
+        u.grid.set_cellstate(1, 2, CellState::Alive);
+        assert!(!u.is_extinct());
+        // a single cell dies
+        u.update();
+        assert!(u.is_extinct());
+    }
+
+    /// Fixed size buffer to render into without allocation.
+    #[cfg(feature = "dead-alive-only")]
+    struct TextBuffer {
+        /// The rendered bytes.
+        bytes: [u8; 64],
+        /// Number of used bytes.
+        len: usize,
+    }
+
+    #[cfg(feature = "dead-alive-only")]
+    impl core::fmt::Write for TextBuffer {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.len + s.len();
+            if end > self.bytes.len() {
+                return Err(core::fmt::Error);
+            }
+            self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
     }
 
     #[test]
-    // check grid creation values
-    fn grid_set_cellstate() {
-        let mut g = Grid::new(3, 17);
-        #[cfg(feature = "dead-alive-only")]
-        g.set_cellstate(1, 8, CellState::Alive);
-        let mut c = g.get_cellstate(1, 8);
-        #[cfg(feature = "dead-alive-only")]
-        assert_eq!(c, &CellState::Alive);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_display() {
+        use core::fmt::Write;
+        let mut g = Grid::new(4, 3);
+        g.set_cellstate(1, 0, CellState::Alive);
+        g.set_cellstate(2, 1, CellState::Alive);
+        g.set_cellstate(0, 2, CellState::Alive);
 
-        // use tuple
-        #[cfg(feature = "dead-alive-only")]
-        g.set_cellstate_hv((2, 5), CellState::Alive);
-        c = g.get_cellstate(2, 5);
-        #[cfg(feature = "dead-alive-only")]
-        assert_eq!(c, &CellState::Alive);
+        let mut buf = TextBuffer {
+            bytes: [0; 64],
+            len: 0,
+        };
+        write!(buf, "{}", g).unwrap();
+        assert_eq!(&buf.bytes[..buf.len], b".O..\n..O.\nO...\n");
+
+        buf.len = 0;
+        write!(buf, "{}", g.display_with('#', ' ')).unwrap();
+        assert_eq!(&buf.bytes[..buf.len], b" #  \n  # \n#   \n");
     }
 
     #[test]
-    #[should_panic]
-    fn grid_set_cell_v_too_large() {
-        let mut g = Grid::new(3, 17);
-        #[cfg(not(feature = "dead-alive-only"))]
-        g.set_cellstate(1, 17, CellState::Dummy);
-        #[cfg(feature = "dead-alive-only")]
-        g.set_cellstate(1, 17, CellState::Alive);
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn grid_render_with() {
+        let mut g = Grid::new(3, 2);
+        g.set_cellstate(0, 0, CellState::Alive);
+        g.set_cellstate(2, 1, CellState::Alive);
+        let blocks = RenderStyle {
+            alive: '█',
+            dead: ' ',
+        };
+        assert_eq!(g.render_with(blocks), "█  \n  █\n");
+        assert_eq!(
+            g.render_with(RenderStyle::default()),
+            alloc::format!("{}", g)
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn grid_set_cell_h_too_large() {
-        let mut g = Grid::new(3, 1);
-        #[cfg(not(feature = "dead-alive-only"))]
-        g.set_cellstate(3, 0, CellState::Dummy);
-        #[cfg(feature = "dead-alive-only")]
-        g.set_cellstate(3, 0, CellState::Alive);
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn grid_render_framed() {
+        let mut g = Grid::new(4, 2);
+        g.set_cellstate(1, 0, CellState::Alive);
+        let framed = g.render_framed();
+        assert_eq!(framed, "┌────┐\n│.O..│\n│....│\n└────┘\n");
+        let lines: Vec<&str> = framed.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|l| l.chars().count() == 6));
+        assert!(lines[0].starts_with('┌') && lines[0].ends_with('┐'));
+        assert!(lines[3].starts_with('└') && lines[3].ends_with('┘'));
+
+        let custom = alloc::format!("{}", g.display_with('#', ' ').framed());
+        assert_eq!(custom, "┌────┐\n│ #  │\n│    │\n└────┘\n");
     }
 
     #[test]
-    fn grid_get_north_coordinate() {
-        let g = Grid::new(3, 4);
-        let mut result = g.get_north_coordinate(1, 2);
-        assert_eq!(result.0, 1);
-        assert_eq!(result.1, 1);
+    #[cfg(feature = "conway")]
+    fn grid_place_glider() {
+        let cases = [
+            (
+                Orientation::SouthEast,
+                [(2, 1), (3, 2), (1, 3), (2, 3), (3, 3)],
+                (1, 1),
+            ),
+            (
+                Orientation::SouthWest,
+                [(2, 1), (1, 2), (3, 3), (2, 3), (1, 3)],
+                (-1, 1),
+            ),
+            (
+                Orientation::NorthEast,
+                [(2, 3), (3, 2), (1, 1), (2, 1), (3, 1)],
+                (1, -1),
+            ),
+            (
+                Orientation::NorthWest,
+                [(2, 3), (1, 2), (3, 1), (2, 1), (1, 1)],
+                (-1, -1),
+            ),
+        ];
+        for (orientation, cells, (dh, dv)) in cases {
+            let mut u = Universe::new(8, 8, rules::game_of_life);
+            u.grid.place_glider((1, 1), orientation);
+            assert_eq!(u.grid.population(), 5);
+            for hv in cells {
+                assert_eq!(u.grid.get_cellstate_hv(hv), &CellState::Alive);
+            }
 
-        result = g.get_north_coordinate(2, 0);
-        assert_eq!(result.0, 2);
-        assert_eq!(result.1, 3);
+            // one cell diagonally after four generations
+            for _ in 0..4 {
+                u.update();
+            }
+            assert_eq!(u.grid.population(), 5);
+            for (h, v) in cells {
+                let h = (h as i16 + dh) as u8;
+                let v = (v as i16 + dv) as u8;
+                assert_eq!(u.grid.get_cellstate(h, v), &CellState::Alive);
+            }
+        }
+
+        // wrapping around the edges
+        let mut g = Grid::new(5, 5);
+        g.place_glider((4, 4), Orientation::SouthEast);
+        for hv in [(0, 4), (1, 0), (4, 1), (0, 1), (1, 1)] {
+            assert_eq!(g.get_cellstate_hv(hv), &CellState::Alive);
+        }
+        assert_eq!(g.population(), 5);
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_north_coordinate_v_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_north_coordinate(0, 4);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_randomize() {
+        let mut a = Grid::new(64, 32);
+        let mut b = Grid::new(64, 32);
+        a.randomize(42, 128);
+        b.randomize(42, 128);
+        assert_eq!(a.population(), b.population());
+        assert!(a == b);
+        // roughly half of the 2048 cells are alive
+        assert!(a.population() > 900 && a.population() < 1150);
+
+        b.randomize(43, 128);
+        assert!(a != b);
+
+        a.randomize(42, 0);
+        assert!(a.is_extinct());
+        a.randomize(0, 255);
+        assert!(a.population() > 2000);
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_north_coordinate_h_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_north_coordinate(1, 2);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_population() {
+        let mut g = Grid::new(7, 5);
+        assert_eq!(g.population(), 0);
+
+        g.set_cellstate(0, 0, CellState::Alive);
+        g.set_cellstate(6, 4, CellState::Alive);
+        g.set_cellstate(3, 2, CellState::Alive);
+        assert_eq!(g.population(), 3);
+
+        g.fill(CellState::Alive);
+        assert_eq!(g.population(), 7 * 5);
     }
 
     #[test]
-    fn grid_get_south_coordinate() {
-        let g = Grid::new(3, 4);
-        let mut result = g.get_south_coordinate(1, 2);
-        assert_eq!(result.0, 1);
-        assert_eq!(result.1, 3);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_population_maximal_grid() {
+        let mut g = Grid::new(u8::MAX, u8::MAX);
+        for h in 0..u8::MAX {
+            for v in 0..u8::MAX {
+                g.set_cellstate(h, v, CellState::Alive);
+            }
+        }
+        assert_eq!(g.population(), 65025);
+    }
 
-        result = g.get_south_coordinate(2, 0);
-        assert_eq!(result.0, 2);
-        assert_eq!(result.1, 1);
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_count_alive_in_row_and_column() {
+        let mut g = Grid::new(6, 4);
+        for h in 0..6u8 {
+            g.set_cellstate(h, 2, CellState::Alive);
+        }
+        g.set_cellstate(3, 0, CellState::Alive);
+
+        assert_eq!(g.count_alive_in_row(0), 1);
+        assert_eq!(g.count_alive_in_row(1), 0);
+        assert_eq!(g.count_alive_in_row(2), 6);
+        assert_eq!(g.count_alive_in_row(3), 0);
+        for h in 0..6u8 {
+            if h == 3 {
+                assert_eq!(g.count_alive_in_column(h), 2);
+            } else {
+                assert_eq!(g.count_alive_in_column(h), 1);
+            }
+        }
     }
 
     #[test]
     #[should_panic]
-    fn grid_get_south_coordinate_v_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_south_coordinate(0, 4);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_count_alive_in_row_too_large() {
+        let g = Grid::new(6, 4);
+        let _ = g.count_alive_in_row(4);
     }
 
     #[test]
     #[should_panic]
-    fn grid_get_south_coordinate_h_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_south_coordinate(1, 2);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_count_alive_in_column_too_large() {
+        let g = Grid::new(6, 4);
+        let _ = g.count_alive_in_column(6);
     }
 
     #[test]
-    fn grid_get_west_coordinate() {
-        let g = Grid::new(3, 4);
-        let mut result = g.get_west_coordinate(1, 2);
-        assert_eq!(result.0, 0);
-        assert_eq!(result.1, 2);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_pack_row() {
+        let mut g = Grid::new(10, 2);
+        g.set_cellstate(0, 1, CellState::Alive);
+        g.set_cellstate(3, 1, CellState::Alive);
+        g.set_cellstate(8, 1, CellState::Alive);
+        g.set_cellstate(9, 1, CellState::Alive);
+        let packed = g.pack_row(1);
+        // the last byte only holds two cells
+        assert_eq!(packed[0], 0b10010000);
+        assert_eq!(packed[1], 0b11000000);
+        assert!(packed[2..].iter().all(|b| *b == 0));
+        assert!(g.pack_row(0).iter().all(|b| *b == 0));
 
-        result = g.get_west_coordinate(0, 2);
-        assert_eq!(result.0, 2);
-        assert_eq!(result.1, 2);
+        // padding bits are ignored
+        let mut h = Grid::new(10, 2);
+        h.unpack_row(1, &[0b10010000, 0b11111111]);
+        assert_eq!(h.pack_row(1), packed);
+        assert_eq!(h.first_difference(&g), None);
     }
 
     #[test]
     #[should_panic]
-    fn grid_get_west_coordinate_v_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_west_coordinate(0, 4);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_unpack_row_too_short() {
+        let mut g = Grid::new(10, 2);
+        g.unpack_row(0, &[0xff]);
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_west_coordinate_h_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_west_coordinate(1, 2);
+    #[cfg(feature = "dead-alive-u8-utils")]
+    fn grid_from_packed_u8_rows() {
+        // A,D,D,A,D,D,D,D A,A
+        // D,A,D,D,D,D,D,D D,A
+        let rows: [&[u8]; 2] = [&[0b10010000, 0b11000000], &[0b01000000, 0b01000000]];
+        let g = Grid::from_packed_u8_rows(10, 2, &rows).unwrap();
+        assert_eq!(g.get_horizontal_size(), 10);
+        assert_eq!(g.get_vertical_size(), 2);
+        assert_eq!(g.population(), 6);
+        assert_eq!(g.get_cellstate(0, 0), &CellState::Alive);
+        assert_eq!(g.get_cellstate(3, 0), &CellState::Alive);
+        assert_eq!(g.get_cellstate(8, 0), &CellState::Alive);
+        assert_eq!(g.get_cellstate(9, 0), &CellState::Alive);
+        assert_eq!(g.get_cellstate(1, 1), &CellState::Alive);
+        assert_eq!(g.get_cellstate(9, 1), &CellState::Alive);
+        assert_eq!(g.get_cellstate(8, 1), &CellState::Dead);
     }
 
     #[test]
-    fn grid_get_northeast_coordinate() {
-        let g = Grid::new(3, 4);
-        let mut result = g.get_northeast_coordinate(1, 2);
-        assert_eq!(result.0, 2);
-        assert_eq!(result.1, 1);
-
-        result = g.get_northeast_coordinate(2, 0);
-        assert_eq!(result.0, 0);
-        assert_eq!(result.1, 3);
+    #[cfg(feature = "dead-alive-u8-utils")]
+    fn grid_from_packed_u8_rows_errors() {
+        let short: [&[u8]; 2] = [&[0xff, 0xff], &[0xff]];
+        assert_eq!(
+            Grid::from_packed_u8_rows(10, 2, &short).unwrap_err(),
+            ParseError::Truncated
+        );
+        let one_row: [&[u8]; 1] = [&[0xff, 0xff]];
+        assert_eq!(
+            Grid::from_packed_u8_rows(10, 2, &one_row).unwrap_err(),
+            ParseError::Truncated
+        );
+        assert_eq!(
+            Grid::from_packed_u8_rows(0, 1, &one_row).unwrap_err(),
+            ParseError::InvalidDimensions
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_northeast_coordinate_v_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_northeast_coordinate(0, 4);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_from_bool_rows() {
+        let rows: [&[bool]; 2] = [&[true, false, false], &[false, true, true]];
+        let g = Grid::from_bool_rows(&rows).unwrap();
+        assert_eq!(g.get_horizontal_size(), 3);
+        assert_eq!(g.get_vertical_size(), 2);
+        for (v, row) in rows.iter().enumerate() {
+            for (h, alive) in row.iter().enumerate() {
+                assert_eq!(
+                    g.get_cellstate(h as u8, v as u8) == &CellState::Alive,
+                    *alive
+                );
+            }
+        }
+
+        let ragged: [&[bool]; 2] = [&[true, false], &[true]];
+        assert_eq!(
+            Grid::from_bool_rows(&ragged).unwrap_err(),
+            GridError::NotRectangular
+        );
+        assert_eq!(
+            Grid::from_bool_rows(&[]).unwrap_err(),
+            GridError::InvalidDimensions
+        );
+        let wide = [false; HORIZONTAL_MAX + 1];
+        assert_eq!(
+            Grid::from_bool_rows(&[&wide]).unwrap_err(),
+            GridError::InvalidDimensions
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_northeast_coordinate_h_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_northeast_coordinate(1, 2);
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn grid_from_nested() {
+        let mut g = Grid::new(4, 3);
+        g.set_cellstate(0, 0, CellState::Alive);
+        g.set_cellstate(3, 1, CellState::Alive);
+        g.set_cellstate(1, 2, CellState::Alive);
+
+        // row by row via the cell iterator
+        let mut cells = g;
+        let nested: Vec<Vec<CellState>> =
+            (0..3).map(|_| cells.by_ref().take(4).collect()).collect();
+        assert_eq!(nested[1][3], CellState::Alive);
+
+        let restored = Grid::from_nested(nested).unwrap();
+        assert_eq!(restored.get_horizontal_size(), 4);
+        assert_eq!(restored.get_vertical_size(), 3);
+        assert_eq!(restored.first_difference(&g), None);
     }
 
     #[test]
-    fn grid_get_southeast_coordinate() {
-        let g = Grid::new(3, 4);
-        let mut result = g.get_southeast_coordinate(1, 2);
-        assert_eq!(result.0, 2);
-        assert_eq!(result.1, 3);
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn grid_to_nested() {
+        let mut g = Grid::new(5, 2);
+        g.set_cellstate(4, 0, CellState::Alive);
+        g.set_cellstate(1, 1, CellState::Alive);
 
-        result = g.get_southeast_coordinate(2, 0);
-        assert_eq!(result.0, 0);
-        assert_eq!(result.1, 1);
+        let nested = g.to_nested();
+        assert_eq!(nested.len(), 2);
+        assert!(nested.iter().all(|row| row.len() == 5));
+        // same order as the cell iterator
+        let flat: Vec<CellState> = nested.into_iter().flatten().collect();
+        let iterated: Vec<CellState> = g.iter_cells().map(|(_, _, cs)| *cs).collect();
+        assert_eq!(flat, iterated);
+
+        assert_eq!(
+            Grid::from_nested(g.to_nested())
+                .unwrap()
+                .first_difference(&g),
+            None
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_southeast_coordinate_v_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_southeast_coordinate(0, 4);
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn grid_to_bool_matrix() {
+        let mut g = Grid::new(7, 5);
+        g.randomize(42, 128);
+        let matrix = g.to_bool_matrix();
+        assert_eq!(matrix.len(), 5);
+        assert!(matrix.iter().all(|row| row.len() == 7));
+        for (h, v, cs) in g.iter_cells() {
+            assert_eq!(matrix[v as usize][h as usize], cs == &CellState::Alive);
+        }
+        let alive = matrix.iter().flatten().filter(|alive| **alive).count();
+        assert_eq!(alive, g.population() as usize);
+
+        let rows: Vec<&[bool]> = matrix.iter().map(|row| row.as_slice()).collect();
+        assert_eq!(
+            Grid::from_bool_rows(&rows).unwrap().first_difference(&g),
+            None
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_southeast_coordinate_h_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_southeast_coordinate(1, 2);
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn grid_from_nested_errors() {
+        assert_eq!(
+            Grid::from_nested(vec![]).unwrap_err(),
+            ParseError::InvalidDimensions
+        );
+        assert_eq!(
+            Grid::from_nested(vec![vec![]]).unwrap_err(),
+            ParseError::InvalidDimensions
+        );
+        assert_eq!(
+            Grid::from_nested(vec![vec![CellState::Dead; 256]]).unwrap_err(),
+            ParseError::InvalidDimensions
+        );
+        assert_eq!(
+            Grid::from_nested(vec![vec![CellState::Dead; 3], vec![CellState::Dead; 2]])
+                .unwrap_err(),
+            ParseError::NotRectangular
+        );
     }
 
     #[test]
-    fn grid_get_southwest_coordinate() {
-        let g = Grid::new(3, 4);
-        let mut result = g.get_southwest_coordinate(1, 2);
-        assert_eq!(result.0, 0);
-        assert_eq!(result.1, 3);
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn grid_packed_bytes_roundtrip() {
+        let mut g = Grid::new(5, 3);
+        g.set_cellstate(0, 0, CellState::Alive);
+        g.set_cellstate(4, 1, CellState::Alive);
+        g.set_cellstate(2, 2, CellState::Alive);
+        let bytes = g.to_packed_bytes();
+        // version, dimensions and two bytes for 15 cells
+        assert_eq!(bytes, [1, 5, 3, 0b10000000, 0b01001000]);
 
-        result = g.get_southwest_coordinate(0, 0);
-        assert_eq!(result.0, 2);
-        assert_eq!(result.1, 1);
+        let restored = Grid::from_packed_bytes(&bytes).unwrap();
+        assert_eq!(restored.get_horizontal_size(), 5);
+        assert_eq!(restored.get_vertical_size(), 3);
+        for h in 0..5u8 {
+            for v in 0..3u8 {
+                assert_eq!(restored.get_cellstate(h, v), g.get_cellstate(h, v));
+            }
+        }
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_southwest_coordinate_v_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_southwest_coordinate(0, 4);
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn grid_packed_bytes_errors() {
+        assert_eq!(
+            Grid::from_packed_bytes(&[42, 5, 3, 0, 0]).unwrap_err(),
+            PackError::UnsupportedVersion(42)
+        );
+        assert_eq!(
+            Grid::from_packed_bytes(&[1, 5, 3, 0]).unwrap_err(),
+            PackError::Truncated
+        );
+        assert_eq!(
+            Grid::from_packed_bytes(&[1, 0, 3]).unwrap_err(),
+            PackError::InvalidDimensions
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_southwest_coordinate_h_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_southwest_coordinate(1, 2);
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn grid_to_pgm() {
+        let mut g = Grid::new(12, 5);
+        g.randomize(3, 128);
+        let pgm = g.to_pgm();
+        let header = b"P5\n12 5\n255\n";
+        assert_eq!(&pgm[..header.len()], header);
+        let pixels = &pgm[header.len()..];
+        assert_eq!(pixels.len(), 60);
+        assert!(pixels.iter().all(|p| *p == 0 || *p == 255));
+        for (h, v) in [(0u8, 0u8), (7, 2), (11, 4)] {
+            let expected = if g.get_cellstate(h, v) == &CellState::Alive {
+                255
+            } else {
+                0
+            };
+            assert_eq!(pixels[v as usize * 12 + h as usize], expected);
+        }
+        assert_eq!(
+            pixels.iter().filter(|p| **p == 255).count(),
+            g.population() as usize
+        );
     }
 
     #[test]
-    fn grid_get_northwest_coordinate() {
-        let g = Grid::new(3, 4);
-        let mut result = g.get_northwest_coordinate(1, 2);
-        assert_eq!(result.0, 0);
-        assert_eq!(result.1, 1);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_iter_alive() {
+        let mut g = Grid::new(9, 7);
+        assert_eq!(g.iter_alive().next(), None);
+
+        let alive = [(4, 0), (0, 2), (8, 2), (3, 6)];
+        for (h, v) in alive {
+            g.set_cellstate(h, v, CellState::Alive);
+        }
+        assert_eq!(g.iter_alive().count(), alive.len());
+        // row by row, so in the order of setting
+        assert!(g.iter_alive().zip(alive).all(|(a, b)| a == b));
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_find_cells() {
+        let mut g = Grid::new(6, 4);
+        g.fill(CellState::Alive);
+        assert_eq!(g.find_cells(CellState::Dead).next(), None);
+
+        let dead = [(2, 0), (5, 1), (0, 3), (4, 3)];
+        for (h, v) in dead {
+            g.set_cellstate(h, v, CellState::Dead);
+        }
+        assert_eq!(g.find_cells(CellState::Dead).count(), dead.len());
+        assert!(g.find_cells(CellState::Dead).zip(dead).all(|(a, b)| a == b));
+        assert_eq!(g.find_cells(CellState::Alive).count(), 20);
+    }
 
-        result = g.get_northwest_coordinate(0, 0);
-        assert_eq!(result.0, 2);
-        assert_eq!(result.1, 3);
+    #[test]
+    #[cfg(feature = "multistate")]
+    fn grid_find_cells_multistate() {
+        let mut g = Grid::new(4, 4);
+        g.set_cellstate(1, 2, CellState(3));
+        g.set_cellstate(3, 0, CellState(3));
+        g.set_cellstate(2, 2, CellState(1));
+        let found: [(u8, u8); 2] = {
+            let mut cells = g.find_cells(CellState(3));
+            [cells.next().unwrap(), cells.next().unwrap()]
+        };
+        assert_eq!(found, [(3, 0), (1, 2)]);
+        assert_eq!(g.find_cells(CellState(0)).count(), 13);
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_northwest_coordinate_v_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_northwest_coordinate(0, 4);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_live_bounding_box() {
+        let mut g = Grid::new(10, 8);
+        assert_eq!(g.live_bounding_box(), None);
+
+        g.set_cellstate(4, 3, CellState::Alive);
+        assert_eq!(g.live_bounding_box(), Some(((4, 3), (4, 3))));
+
+        g.set_cellstate(7, 1, CellState::Alive);
+        g.set_cellstate(2, 6, CellState::Alive);
+        assert_eq!(g.live_bounding_box(), Some(((2, 1), (7, 6))));
+
+        // no wrapping around the edges
+        g.set_cellstate(9, 7, CellState::Alive);
+        g.set_cellstate(0, 0, CellState::Alive);
+        assert_eq!(g.live_bounding_box(), Some(((0, 0), (9, 7))));
     }
 
     #[test]
-    #[should_panic]
-    fn grid_get_northwest_coordinate_h_too_large() {
-        let g = Grid::new(1, 4);
-        let _ = g.get_northwest_coordinate(1, 2);
+    #[cfg(feature = "dead-alive-only")]
+    fn grid_serialize_to() {
+        let mut g = Grid::new(20, 10);
+        g.randomize(7, 96);
+        assert_eq!(g.packed_len(), 28);
+
+        let mut buf = [0xffu8; 32];
+        assert_eq!(g.serialize_to(&mut buf), Ok(28));
+        assert_eq!(buf[..3], [1, 20, 10]);
+        // the remaining bytes stay untouched
+        assert_eq!(buf[28..], [0xff; 4]);
+
+        let restored = Grid::deserialize_from(&buf).unwrap();
+        assert_eq!(restored.get_horizontal_size(), 20);
+        assert_eq!(restored.get_vertical_size(), 10);
+        assert_eq!(restored.first_difference(&g), None);
+
+        let mut small = [0u8; 27];
+        assert_eq!(g.serialize_to(&mut small), Err(PackError::BufferTooSmall));
+        assert_eq!(
+            Grid::deserialize_from(&buf[..27]).unwrap_err(),
+            PackError::Truncated
+        );
     }
 
     #[test]
+    #[cfg(feature = "dead-alive-only")]
     fn grid_next_byte() {
         // D,A,D,D,D,A,A,A -> 01000111
         // A,A,D,D,A,D,A,D -> 11001010
@@ -869,6 +5243,7 @@ mod tests {
         assert_eq!(None, g.next_byte());
     }
     #[test]
+    #[cfg(feature = "dead-alive-only")]
     fn grid_next() {
         // D,D,A
         // D,A,D
@@ -902,43 +5277,75 @@ mod tests {
             for v in 0..6u8 {
                 let cs = u1.grid.get_cellstate(h, v);
                 #[cfg(not(feature = "dead-alive-only"))]
-                assert_eq!(cs, &CellState::Dummy);
+                assert_eq!(cs, &CellState::default());
                 #[cfg(feature = "dead-alive-only")]
                 assert_eq!(cs, &CellState::Dead);
             }
         }
 
-        fn inversion(h: u8, v: u8, g: &Grid) -> CellState {
-            match g.get_cellstate(h, v) {
-                &CellState::Alive => CellState::Dead,
-                &CellState::Dead => CellState::Alive,
+        #[cfg(feature = "dead-alive-only")]
+        {
+            fn inversion(h: u8, v: u8, g: &Grid) -> CellState {
+                match *g.get_cellstate(h, v) {
+                    CellState::Alive => CellState::Dead,
+                    CellState::Dead => CellState::Alive,
+                }
+            }
+
+            let mut u2 = Universe::new(4, 6, inversion);
+            u2.update();
+            for h in 0..4u8 {
+                for v in 0..6u8 {
+                    let cs = u2.grid.get_cellstate(h, v);
+                    assert_eq!(cs, &CellState::Alive);
+                }
             }
         }
+    }
 
-        let mut u2 = Universe::new(4, 6, inversion);
-        u2.update();
-        for h in 0..4u8 {
-            for v in 0..6u8 {
-                let cs = u2.grid.get_cellstate(h, v);
-                assert_eq!(cs, &CellState::Alive);
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_new_with_closure() {
+        let threshold: u8 = 1;
+        // births cells with more living neighbours than the threshold
+        let mut u = Universe::new_with_closure(5, 5, move |h, v, g: &Grid| {
+            if g.count_live_neighbours(h, v) > threshold {
+                CellState::Alive
+            } else {
+                *g.get_cellstate(h, v)
             }
+        });
+        u.grid.set_cellstate(1, 1, CellState::Alive);
+        u.grid.set_cellstate(3, 1, CellState::Alive);
+        u.update();
+        // only the cells between both living ones see two of them
+        assert_eq!(u.grid.population(), 5);
+        for hv in [(2, 0), (2, 1), (2, 2)] {
+            assert_eq!(u.grid.get_cellstate_hv(hv), &CellState::Alive);
         }
+        assert_eq!(u.grid.get_cellstate(0, 0), &CellState::Dead);
+        assert_eq!(u.evaluate_cell(1, 2), CellState::Alive);
+        assert_eq!(u.evaluate_cell(2, 3), CellState::Dead);
+        assert_eq!(u.generation(), 1);
     }
 
     #[test]
     #[cfg(feature = "dead-alive-only")]
     fn universe_automaton() {
         fn inversion(h: u8, v: u8, g: &Grid) -> CellState {
-            match g.get_cellstate(h, v) {
-                &CellState::Alive => CellState::Dead,
-                &CellState::Dead => CellState::Alive,
+            match *g.get_cellstate(h, v) {
+                CellState::Alive => CellState::Dead,
+                CellState::Dead => CellState::Alive,
             }
         }
 
         let u = Universe::new(1, 1, inversion);
         assert_eq!(u.grid.get_cellstate(0, 0), &CellState::Dead);
 
-        let state = (u.automaton)(0, 0, &u.grid);
+        let state = match u.automaton {
+            Automaton::Plain(rules) => rules(0, 0, &u.grid),
+            _ => panic!("plain rule expected"),
+        };
         assert_eq!(state, CellState::Alive);
     }
 
@@ -946,9 +5353,9 @@ mod tests {
     #[cfg(feature = "dead-alive-only")]
     fn universe_update_one_cell_inversion() {
         fn inversion(h: u8, v: u8, g: &Grid) -> CellState {
-            match g.get_cellstate(h, v) {
-                &CellState::Alive => CellState::Dead,
-                &CellState::Dead => CellState::Alive,
+            match *g.get_cellstate(h, v) {
+                CellState::Alive => CellState::Dead,
+                CellState::Dead => CellState::Alive,
             }
         }
 
@@ -960,10 +5367,711 @@ mod tests {
         assert_eq!(u.grid.get_cellstate(0, 0), &CellState::Alive);
 
         // reset via inversion rule
-        u.update(); // TODO: -> calling update seems to fail moditying the states
-                    //assert_eq!(u.shadow.get_cellstate(0, 0), &CellState::Alive); // sanity check on shadow state -> fails
-                    //u.grid.set_cellstate(0,0,CellState::Dead);  // this works
-        assert_eq!(u.grid.get_cellstate(0, 0), &CellState::Dead); // this fails
+        u.update();
+        // shadow holds the previous generation
+        assert_eq!(u.shadow.get_cellstate(0, 0), &CellState::Alive);
+        assert_eq!(u.grid.get_cellstate(0, 0), &CellState::Dead);
+
+        // and back again
+        u.update();
+        assert_eq!(u.shadow.get_cellstate(0, 0), &CellState::Dead);
+        assert_eq!(u.grid.get_cellstate(0, 0), &CellState::Alive);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_update_keeps_grid_settings() {
+        fn inversion(h: u8, v: u8, g: &Grid) -> CellState {
+            match *g.get_cellstate(h, v) {
+                CellState::Alive => CellState::Dead,
+                CellState::Dead => CellState::Alive,
+            }
+        }
+
+        let mut u = Universe::new(4, 4, inversion);
+        u.grid = Grid::with_axis_boundaries(4, 4, BoundaryMode::Wrap, BoundaryMode::Fixed);
+        u.update();
+        assert_eq!(u.grid.get_cellstate(0, 0), &CellState::Alive);
+        u.update();
+        assert_eq!(u.grid.get_vertical_boundary(), BoundaryMode::Fixed);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_evaluate_cell() {
+        // horizontal blinker
+        let mut u = Universe::new(5, 5, conway);
+        u.grid.set_cellstate(1, 2, CellState::Alive);
+        u.grid.set_cellstate(2, 2, CellState::Alive);
+        u.grid.set_cellstate(3, 2, CellState::Alive);
+
+        assert_eq!(u.evaluate_cell(2, 2), CellState::Alive);
+        assert_eq!(u.evaluate_cell(1, 2), CellState::Dead);
+        assert_eq!(u.evaluate_cell(3, 2), CellState::Dead);
+        assert_eq!(u.evaluate_cell(2, 1), CellState::Alive);
+        assert_eq!(u.evaluate_cell(2, 3), CellState::Alive);
+
+        // nothing was modified
+        assert_eq!(u.grid.get_cellstate(1, 2), &CellState::Alive);
+        assert_eq!(u.grid.get_cellstate(2, 1), &CellState::Dead);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_update_local() {
+        let mut u = Universe::new(4, 3, conway);
+        u.grid.set_cellstate(1, 2, CellState::Alive);
+        u.update_local(|cs| match cs {
+            CellState::Alive => CellState::Dead,
+            CellState::Dead => CellState::Alive,
+        });
+        for h in 0..4u8 {
+            for v in 0..3u8 {
+                if (h, v) == (1, 2) {
+                    assert_eq!(u.grid.get_cellstate(h, v), &CellState::Dead);
+                } else {
+                    assert_eq!(u.grid.get_cellstate(h, v), &CellState::Alive);
+                }
+                // the shadow grid was not involved
+                assert_eq!(u.shadow.get_cellstate(h, v), &CellState::Dead);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_update_local_generation() {
+        let invert = |cs| match cs {
+            CellState::Alive => CellState::Dead,
+            CellState::Dead => CellState::Alive,
+        };
+        let mut u = Universe::new(4, 3, conway);
+        u.grid.set_cellstate(1, 2, CellState::Alive);
+        u.reset_to(u.grid);
+        let seed = u.grid;
+
+        // counts like any other update
+        u.update_local(invert);
+        assert_eq!(u.generation(), 1);
+        u.update();
+        assert_eq!(u.generation(), 2);
+        u.set_paused(true);
+        u.update_local(invert);
+        assert_eq!(u.generation(), 2);
+        u.set_paused(false);
+
+        u.update_local(invert);
+        assert_eq!(u.generation(), 3);
+        #[cfg(feature = "history")]
+        assert_eq!(u.population_history(), &[11, 0, 12]);
+
+        // the starting grid is restored
+        u.reset();
+        assert_eq!(u.generation(), 0);
+        assert!(u.grid == seed);
+        #[cfg(feature = "history")]
+        assert!(u.population_history().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_generation() {
+        let mut u = Universe::new(4, 4, conway);
+        assert_eq!(u.generation(), 0);
+        u.update();
+        u.update();
+        u.update();
+        assert_eq!(u.generation(), 3);
+
+        u.set_paused(true);
+        u.update();
+        assert_eq!(u.generation(), 3);
+
+        u.reset_to(Grid::new(4, 4));
+        assert_eq!(u.generation(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_reset_to() {
+        let mut u = Universe::new(6, 6, conway);
+        // glider
+        for (h, v) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            u.grid.set_cellstate(h, v, CellState::Alive);
+        }
+        let seed = u.grid;
+        let mut frames = [seed; 8];
+        for frame in frames.iter_mut() {
+            u.update();
+            *frame = u.grid;
+        }
+
+        u.reset_to(seed);
+        assert_eq!(u.grid.first_difference(&seed), None);
+        for frame in frames.iter() {
+            u.update();
+            assert_eq!(u.grid.first_difference(frame), None);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "history")]
+    fn universe_population_history() {
+        // a diagonal line shrinks to a single cell and dies out
+        let mut u = Universe::new(8, 8, conway);
+        u.grid.set_cellstate(2, 2, CellState::Alive);
+        u.grid.set_cellstate(3, 3, CellState::Alive);
+        u.grid.set_cellstate(4, 4, CellState::Alive);
+        assert!(u.population_history().is_empty());
+        for _ in 0..3 {
+            u.update();
+        }
+        assert_eq!(u.population_history(), [1, 0, 0]);
+
+        u.reset();
+        assert!(u.population_history().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "history")]
+    fn universe_population_history_full() {
+        // living cells spread east, one cell per update
+        fn grow(h: u8, v: u8, g: &Grid) -> CellState {
+            if h > 0 && g.get_cellstate(h - 1, v) == &CellState::Alive {
+                return CellState::Alive;
+            }
+            *g.get_cellstate(h, v)
+        }
+
+        let mut u = Universe::new(255, 1, grow);
+        u.grid.set_cellstate(0, 0, CellState::Alive);
+        let updates = HISTORY_CAPACITY as u32 + 6;
+        for _ in 0..updates {
+            u.update();
+        }
+        let history = u.population_history();
+        assert_eq!(history.len(), HISTORY_CAPACITY);
+        // the oldest populations were dropped
+        assert_eq!(history[0], 8);
+        assert_eq!(history[HISTORY_CAPACITY - 1], updates + 1);
+        assert!(history.windows(2).all(|w| w[1] == w[0] + 1));
+    }
+
+    /// Check that a Life-like universe matches the Life-like rule
+    /// on a random grid for some generations.
+    ///
+    /// # Arguments
+    /// * `fast`: update via `update_fast()` instead of `update()`
+    #[cfg(feature = "fast-life")]
+    fn assert_fast_matches(
+        birth: &[u8],
+        survival: &[u8],
+        seed: u64,
+        topology: Topology,
+        fast: bool,
+    ) {
+        let mut slow = Universe::new_with_closure(40, 30, rules::life_like(birth, survival));
+        slow.grid = Grid::with_topology(40, 30, topology);
+        slow.grid.randomize(seed, 90);
+        let mut u = Universe::new_life_like(40, 30, birth, survival);
+        u.grid = slow.grid;
+        for _ in 0..50 {
+            slow.update();
+            if fast {
+                u.update_fast();
+            } else {
+                u.update();
+            }
+            assert!(u.grid == slow.grid);
+        }
+        assert_eq!(u.generation(), 50);
+    }
+
+    #[test]
+    #[cfg(feature = "fast-life")]
+    fn universe_update_fast() {
+        for fast in [true, false] {
+            assert_fast_matches(&[3], &[2, 3], 1, Topology::Toroidal, fast);
+            assert_fast_matches(&[3], &[2, 3], 2, Topology::Bounded, fast);
+            // HighLife
+            assert_fast_matches(&[3, 6], &[2, 3], 3, Topology::Toroidal, fast);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "fast-life")]
+    fn universe_update_fast_fallback() {
+        // other rules fall back to update()
+        let mut u = Universe::new(40, 30, conway);
+        u.grid.randomize(4, 90);
+        let mut expected = u;
+        u.update_fast();
+        expected.update();
+        assert!(u.grid == expected.grid);
+        assert_eq!(u.generation(), 1);
+    }
+
+    #[test]
+    #[cfg(all(feature = "rayon", feature = "conway"))]
+    fn universe_update_parallel() {
+        let mut serial = Universe::new(200, 150, rules::game_of_life);
+        serial.grid.randomize(11, 80);
+        let mut parallel = Universe::new(200, 150, rules::game_of_life);
+        parallel.grid = serial.grid;
+        for _ in 0..20 {
+            serial.update();
+            parallel.update_parallel();
+            assert!(parallel.grid == serial.grid);
+        }
+        assert_eq!(parallel.generation(), 20);
+
+        // pausing applies as well
+        parallel.set_paused(true);
+        let before = parallel.grid;
+        parallel.update_parallel();
+        assert!(parallel.grid == before);
+        assert_eq!(parallel.generation(), 20);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_reset() {
+        let mut u = Universe::new(16, 16, conway);
+        u.grid.randomize(5, 80);
+        let seed = u.grid;
+        // no update yet
+        u.reset();
+        assert!(u.grid == seed);
+        for _ in 0..10 {
+            u.update();
+        }
+        assert_eq!(u.generation(), 10);
+        assert!(u.grid != seed);
+
+        u.reset();
+        assert_eq!(u.generation(), 0);
+        assert!(u.grid == seed);
+
+        // runs identically again
+        let mut other = Universe::new(16, 16, conway);
+        other.grid = seed;
+        for _ in 0..10 {
+            u.update();
+            other.update();
+            assert!(u.grid == other.grid);
+        }
+
+        // reset_to() replaces the seed
+        let empty = Grid::new(16, 16);
+        u.reset_to(empty);
+        u.update();
+        u.reset();
+        assert!(u.grid == empty);
+
+        // a new seed after a reset is captured by the next update
+        u.grid.set_cellstate(3, 3, CellState::Alive);
+        u.update();
+        u.reset();
+        assert_eq!(u.grid.population(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_reset_to_wrong_size() {
+        let mut u = Universe::new(6, 6, conway);
+        u.reset_to(Grid::new(6, 5));
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_preview_change_count() {
+        // blinker: two cells die, two are born
+        let mut u = Universe::new(5, 5, conway);
+        for h in 1..4 {
+            u.grid.set_cellstate(h, 2, CellState::Alive);
+        }
+        let before = u.grid;
+        assert_eq!(u.preview_change_count(), 4);
+        assert_eq!(u.grid.first_difference(&before), None);
+
+        // block: still life
+        let mut u = Universe::new(5, 5, conway);
+        for (h, v) in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+            u.grid.set_cellstate(h, v, CellState::Alive);
+        }
+        let before = u.grid;
+        assert_eq!(u.preview_change_count(), 0);
+        assert_eq!(u.grid.first_difference(&before), None);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_update_until_extinct() {
+        // diagonal line shrinks to its center and dies
+        let mut u1 = Universe::new(8, 8, conway);
+        u1.grid.set_cellstate(1, 1, CellState::Alive);
+        u1.grid.set_cellstate(2, 2, CellState::Alive);
+        u1.grid.set_cellstate(3, 3, CellState::Alive);
+        assert_eq!(u1.update_until_extinct(10), Some(2));
+        assert_eq!(u1.update_until_extinct(10), Some(0));
+
+        // block (still life) survives
+        let mut u2 = Universe::new(8, 8, conway);
+        u2.grid.set_cellstate(1, 1, CellState::Alive);
+        u2.grid.set_cellstate(2, 1, CellState::Alive);
+        u2.grid.set_cellstate(1, 2, CellState::Alive);
+        u2.grid.set_cellstate(2, 2, CellState::Alive);
+        assert_eq!(u2.update_until_extinct(10), None);
+        assert_eq!(u2.grid.population(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_update_until_stable() {
+        // block: still life
+        let mut u = Universe::new(6, 6, conway);
+        for hv in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+        assert_eq!(u.update_until_stable(10), Some(0));
+
+        // three cells in an L shape become a block
+        let mut u = Universe::new(6, 6, conway);
+        for hv in [(1, 1), (2, 1), (1, 2)] {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+        assert_eq!(u.update_until_stable(10), Some(1));
+        assert_eq!(u.generation(), 2);
+
+        // glider keeps moving on a torus
+        let mut u = Universe::new(6, 6, conway);
+        for hv in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+        assert_eq!(u.update_until_stable(30), None);
+        assert_eq!(u.generation(), 30);
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn universe_update_active_mask() {
+        // circle of radius 3 around the center
+        let mut mask = [false; 81];
+        for v in 0..9i32 {
+            for h in 0..9i32 {
+                mask[(v * 9 + h) as usize] = (h - 4).pow(2) + (v - 4).pow(2) <= 9;
+            }
+        }
+
+        // (5, 1) lies outside the circle but has three living neighbours
+        let mut u = Universe::new(9, 9, conway);
+        u.grid.set_cellstate(4, 1, CellState::Alive);
+        u.grid.set_cellstate(4, 2, CellState::Alive);
+        u.grid.set_cellstate(5, 2, CellState::Alive);
+        let mut unmasked = u;
+        unmasked.update();
+        assert_eq!(unmasked.grid.get_cellstate(5, 1), &CellState::Alive);
+
+        u.grid.set_active_mask(&mask);
+        for _ in 0..5 {
+            u.update();
+            for v in 0..9u8 {
+                for h in 0..9u8 {
+                    if !mask[v as usize * 9 + h as usize] {
+                        assert_eq!(u.grid.get_cellstate(h, v), &CellState::Dead);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_run_hash() {
+        // glider
+        let mut u1 = Universe::new(8, 8, conway);
+        u1.grid.set_cellstate(1, 0, CellState::Alive);
+        u1.grid.set_cellstate(2, 1, CellState::Alive);
+        u1.grid.set_cellstate(0, 2, CellState::Alive);
+        u1.grid.set_cellstate(1, 2, CellState::Alive);
+        u1.grid.set_cellstate(2, 2, CellState::Alive);
+        let mut u2 = u1;
+        let mut u3 = u1;
+        u3.grid.set_cellstate(6, 6, CellState::Alive);
+
+        let hash = u1.run_hash(12);
+        assert_eq!(hash, u2.run_hash(12));
+        assert_ne!(hash, u3.run_hash(12));
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn universe_serialize_delta() {
+        // horizontal blinker
+        let mut u = Universe::new(5, 5, conway);
+        u.grid.set_cellstate(1, 2, CellState::Alive);
+        u.grid.set_cellstate(2, 2, CellState::Alive);
+        u.grid.set_cellstate(3, 2, CellState::Alive);
+        let mut phase = u.grid;
+        u.update();
+
+        let delta = u.serialize_delta(&phase);
+        // two cells died, two were born
+        assert_eq!(delta.len(), 4 * 3);
+        assert!(u.serialize_delta(&u.grid).is_empty());
+
+        phase.apply_delta(&delta).unwrap();
+        assert_eq!(phase.fingerprint(), u.grid.fingerprint());
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn universe_detect_cycle() {
+        // blinker
+        let mut u = Universe::new(5, 5, conway);
+        for hv in [(1, 2), (2, 2), (3, 2)] {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+        assert_eq!(u.detect_cycle(4, 10), Some(2));
+        assert_eq!(u.generation(), 2);
+
+        // block
+        let mut u = Universe::new(4, 4, conway);
+        for hv in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+        assert_eq!(u.detect_cycle(4, 10), Some(1));
+
+        // a glider returns after crossing the 6x6 torus (24 generations)
+        let mut u = Universe::new(6, 6, conway);
+        for hv in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+        let mut v = u;
+        assert_eq!(u.detect_cycle(10, 30), None);
+        assert_eq!(u.generation(), 30);
+        assert_eq!(v.detect_cycle(30, 30), Some(24));
+        assert_eq!(v.detect_cycle(0, 30), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn universe_record_frames() {
+        // horizontal blinker
+        let mut u = Universe::new(5, 5, conway);
+        u.grid.set_cellstate(1, 2, CellState::Alive);
+        u.grid.set_cellstate(2, 2, CellState::Alive);
+        u.grid.set_cellstate(3, 2, CellState::Alive);
+
+        let frames = u.record_frames(2);
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].get_cellstate(1, 2), &CellState::Alive);
+        assert_eq!(frames[0].get_cellstate(2, 1), &CellState::Dead);
+        assert_eq!(frames[1].get_cellstate(1, 2), &CellState::Dead);
+        assert_eq!(frames[1].get_cellstate(2, 1), &CellState::Alive);
+        assert_eq!(frames[1].get_cellstate(2, 3), &CellState::Alive);
+        assert_eq!(frames[0].fingerprint(), frames[2].fingerprint());
+        assert_eq!(frames[2].fingerprint(), u.grid.fingerprint());
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_set_paused() {
+        // horizontal blinker
+        let mut u = Universe::new(5, 5, conway);
+        u.grid.set_cellstate(1, 2, CellState::Alive);
+        u.grid.set_cellstate(2, 2, CellState::Alive);
+        u.grid.set_cellstate(3, 2, CellState::Alive);
+        let initial = u.grid.fingerprint();
+
+        u.set_paused(true);
+        assert!(u.is_paused());
+        for _ in 0..3 {
+            u.update();
+            assert_eq!(u.grid.fingerprint(), initial);
+        }
+
+        // resume with the rule still in place
+        u.set_paused(false);
+        assert!(!u.is_paused());
+        u.update();
+        assert_eq!(u.grid.get_cellstate(2, 1), &CellState::Alive);
+        assert_eq!(u.grid.get_cellstate(1, 2), &CellState::Dead);
+        u.update();
+        assert_eq!(u.grid.fingerprint(), initial);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_new_with_context() {
+        struct Threshold {
+            min_alive: u8,
+        }
+        fn rule(h: u8, v: u8, g: &Grid, ctx: &Threshold) -> CellState {
+            if g.count_alive_moore_inclusive(h, v) >= ctx.min_alive {
+                CellState::Alive
+            } else {
+                CellState::Dead
+            }
+        }
+
+        let mut u = Universe::new_with_context(5, 5, Threshold { min_alive: 1 }, rule);
+        u.grid.set_cellstate(2, 2, CellState::Alive);
+        u.update();
+        // the block around the living cell comes alive
+        assert_eq!(u.grid.population(), 9);
+
+        u.context_mut().min_alive = 9;
+        assert_eq!(u.context().min_alive, 9);
+        u.update();
+        // only the center of the block is surrounded
+        assert_eq!(u.grid.population(), 1);
+        assert_eq!(u.grid.get_cellstate(2, 2), &CellState::Alive);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_update_with() {
+        let mut u = Universe::new(9, 9, conway);
+        // blinker and a lonely cell dying in the first generation
+        for hv in [(1, 2), (2, 2), (3, 2), (6, 6)] {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+
+        let mut populations = [0u32; 5];
+        populations[0] = u.grid.population();
+        let mut record = |g: &Grid, generation: u64| {
+            populations[generation as usize] = g.population();
+        };
+        for _ in 0..4 {
+            u.update_with(&mut record);
+        }
+        assert_eq!(populations, [4, 3, 3, 3, 3]);
+        assert_eq!(u.generation(), 4);
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "dead-alive-only"))]
+    fn universe_update_timed() {
+        // horizontal blinker
+        let mut u = Universe::new(64, 64, conway);
+        u.grid.set_cellstate(1, 2, CellState::Alive);
+        u.grid.set_cellstate(2, 2, CellState::Alive);
+        u.grid.set_cellstate(3, 2, CellState::Alive);
+        let mut reference = u;
+
+        let duration = u.update_timed();
+        assert!(duration > core::time::Duration::ZERO);
+        reference.update();
+        assert_eq!(u.grid.first_difference(&reference.grid), None);
+        assert_eq!(u.grid.get_cellstate(2, 1), &CellState::Alive);
+    }
+
+    #[test]
+    #[cfg(feature = "age-tracking")]
+    fn universe_cell_age() {
+        // block (still life) and vertical blinker
+        let mut u = Universe::new(10, 10, conway);
+        u.grid.set_cellstate(1, 1, CellState::Alive);
+        u.grid.set_cellstate(2, 1, CellState::Alive);
+        u.grid.set_cellstate(1, 2, CellState::Alive);
+        u.grid.set_cellstate(2, 2, CellState::Alive);
+        u.grid.set_cellstate(6, 5, CellState::Alive);
+        u.grid.set_cellstate(6, 6, CellState::Alive);
+        u.grid.set_cellstate(6, 7, CellState::Alive);
+        for _ in 0..5 {
+            u.update();
+        }
+        assert_eq!(u.cell_age(1, 1), 5);
+        assert_eq!(u.cell_age(6, 6), 5);
+        // born in the last update
+        assert_eq!(u.cell_age(5, 6), 0);
+        assert_eq!(u.grid.get_cellstate(5, 6), &CellState::Alive);
+        // dead
+        assert_eq!(u.cell_age(6, 5), 0);
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "age-tracking"))]
+    fn universe_age_rgb_buffer() {
+        // block (still life) and vertical blinker
+        let mut u = Universe::new(10, 10, conway);
+        u.grid.set_cellstate(1, 1, CellState::Alive);
+        u.grid.set_cellstate(2, 1, CellState::Alive);
+        u.grid.set_cellstate(1, 2, CellState::Alive);
+        u.grid.set_cellstate(2, 2, CellState::Alive);
+        u.grid.set_cellstate(6, 5, CellState::Alive);
+        u.grid.set_cellstate(6, 6, CellState::Alive);
+        u.grid.set_cellstate(6, 7, CellState::Alive);
+        for _ in 0..5 {
+            u.update();
+        }
+
+        let buffer = u.age_rgb_buffer(5);
+        assert_eq!(buffer.len(), 10 * 10 * 3);
+        let pixel = |h: usize, v: usize| &buffer[(v * 10 + h) * 3..(v * 10 + h) * 3 + 3];
+        // old cell of the block
+        assert_eq!(pixel(1, 1), [64, 0, 0]);
+        // newborn end of the blinker
+        assert_eq!(pixel(5, 6), [255, 255, 0]);
+        // dead cell
+        assert_eq!(pixel(0, 0), [0, 0, 0]);
+    }
+
+    // the swapping update has to yield the same generations
+    // as computing into a copy and copying every cell back
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_update_matches_copy_back() {
+        let mut u = Universe::new(16, 12, conway);
+        // R-pentomino, keeps changing for a long time
+        for hv in [(7, 4), (8, 4), (6, 5), (7, 5), (7, 6)] {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+        let mut reference = u.grid;
+
+        for _ in 0..100 {
+            let mut next = reference;
+            for h in 0..16 {
+                for v in 0..12 {
+                    next.set_cellstate(h, v, conway(h, v, &reference));
+                }
+            }
+            for h in 0..16 {
+                for v in 0..12 {
+                    reference.set_cellstate(h, v, *next.get_cellstate(h, v));
+                }
+            }
+
+            u.update();
+            assert!(u.grid == reference);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn universe_update_rule30_rows() {
+        let rows = [
+            ".....O.....",
+            "....OOO....",
+            "...OO..O...",
+            "..OO.OOOO..",
+            ".OO..O...O.",
+        ];
+        let mut u = Universe::new(11, 1, rules::elementary_rule(30));
+        u.grid.set_cellstate(5, 0, CellState::Alive);
+        for row in rows.iter() {
+            for (h, c) in row.chars().enumerate() {
+                let expected = if c == 'O' {
+                    CellState::Alive
+                } else {
+                    CellState::Dead
+                };
+                assert_eq!(u.grid.get_cellstate(h as u8, 0), &expected);
+            }
+            u.update();
+        }
     }
 
     // test based on Wolfram rule 30
@@ -972,26 +6080,6 @@ mod tests {
     #[test]
     #[cfg(feature = "dead-alive-only")]
     fn universe_update_rule30() {
-        fn rule30(h: u8, v: u8, g: &Grid) -> CellState {
-            let left = g.get_west_coordinate(h, v);
-            let right = g.get_east_coordinate(h, v);
-            let state = (
-                g.get_cellstate_hv(left),
-                g.get_cellstate(h, v),
-                g.get_cellstate_hv(right),
-            );
-            return match state {
-                (CellState::Alive, CellState::Alive, CellState::Alive) => CellState::Dead,
-                (CellState::Alive, CellState::Alive, CellState::Dead) => CellState::Dead,
-                (CellState::Alive, CellState::Dead, CellState::Alive) => CellState::Dead,
-                (CellState::Alive, CellState::Dead, CellState::Dead) => CellState::Alive,
-                (CellState::Dead, CellState::Alive, CellState::Alive) => CellState::Alive,
-                (CellState::Dead, CellState::Alive, CellState::Dead) => CellState::Alive,
-                (CellState::Dead, CellState::Dead, CellState::Alive) => CellState::Alive,
-                (CellState::Dead, CellState::Dead, CellState::Dead) => CellState::Dead,
-            };
-        }
-
         // test on dead universe -> should stay dead
         let mut u1 = Universe::new(3, 1, rule30);
         u1.update();
@@ -1022,12 +6110,13 @@ mod tests {
         assert_eq!(CellState::Alive, rule30(2, 0, &u2.grid));
 
         // all cells become alive in first iteration (apply the rule)
+        let previous = u2.grid;
         u2.update();
 
-        // test shadow state
-        assert_eq!(u2.shadow.get_cellstate(0, 0), &CellState::Alive);
-        assert_eq!(u2.shadow.get_cellstate(1, 0), &CellState::Alive);
-        assert_eq!(u2.shadow.get_cellstate(2, 0), &CellState::Alive);
+        // test shadow state (previous generation)
+        for h in 0..3u8 {
+            assert_eq!(u2.shadow.get_cellstate(h, 0), previous.get_cellstate(h, 0));
+        }
 
         // test public state
         assert_eq!(u2.grid.get_cellstate(0, 0), &CellState::Alive);
@@ -1072,20 +6161,52 @@ mod tests {
         assert_eq!(result, 0b10010000);
     }
 
+    #[test]
+    #[cfg(feature = "dead-alive-u8-utils")]
+    fn util_cs8_into_u8_lsb() {
+        let mut group = [&CellState::Dead; 8];
+        assert_eq!(cs8_into_u8_lsb(group), 0b00000000);
+
+        group[0] = &CellState::Alive;
+        assert_eq!(cs8_into_u8_lsb(group), 0b00000001);
+        assert_eq!(cs8_into_u8(group), 0b10000000);
+
+        group[3] = &CellState::Alive;
+        group[6] = &CellState::Alive;
+        assert_eq!(cs8_into_u8_lsb(group), 0b01001001);
+        assert_eq!(cs8_into_u8_lsb(group), cs8_into_u8(group).reverse_bits());
+    }
+
     #[test]
     #[cfg(feature = "dead-alive-u8-utils")]
     fn util_u8_into_cs8() {
         // test defaults
-        let mut expectation = [&CellState::Dead; 8];
+        let mut expectation = [CellState::Dead; 8];
         let mut result = u8_into_cs8(0);
         assert_eq!(result, expectation);
 
-        expectation[7] = &CellState::Alive;
+        expectation[7] = CellState::Alive;
         result = u8_into_cs8(1);
         assert_eq!(result, expectation);
 
-        expectation[0] = &CellState::Alive;
+        expectation[0] = CellState::Alive;
         result = u8_into_cs8(129);
         assert_eq!(result, expectation);
     }
+
+    #[test]
+    #[cfg(feature = "dead-alive-u8-utils")]
+    fn util_u8_cs8_roundtrip() {
+        for bits in [
+            0b00000000, 0b10010000, 0b00000001, 0b10000000, 0b01010101, 0b11111111,
+        ] {
+            assert_eq!(cs8_into_u8(u8_into_cs8(bits).each_ref()), bits);
+        }
+
+        let mut group = [CellState::Dead; 8];
+        group[0] = CellState::Alive;
+        group[3] = CellState::Alive;
+        assert_eq!(u8_into_cs8(cs8_into_u8(group.each_ref())), group);
+        assert_eq!(u8_into_cs8(0b10010000), group);
+    }
 }
@@ -0,0 +1,92 @@
+//! Import of patterns in the Life 1.06 format,
+//! a list of the coordinates of all living cells.
+use crate::{CellState, Grid, PatternError};
+
+/// Decode a Life 1.06 pattern and hand the coordinates
+/// of every living cell to `place`.
+///
+/// # Arguments
+/// * `text`: the pattern
+/// * `place`: called for every living cell
+fn decode<F: FnMut(i32, i32)>(text: &str, mut place: F) -> Result<(), PatternError> {
+    for line in text.lines() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let mut values = line.split_whitespace().map(|value| value.parse::<i32>());
+        match (values.next(), values.next(), values.next()) {
+            (Some(Ok(x)), Some(Ok(y)), None) => place(x, y),
+            _ => return Err(PatternError::InvalidCoordinate),
+        }
+    }
+    Ok(())
+}
+
+impl Grid {
+    /// Load a pattern in the Life 1.06 format into the grid. Every
+    /// line holds the horizontal and vertical coordinate of a living
+    /// cell, lines starting with `#` (e.g. the `#Life 1.06` header)
+    /// are comments and empty lines are skipped. Coordinates outside
+    /// of the grid (including negative ones) wrap around the edges.
+    /// The grid is left untouched if the pattern is invalid.
+    ///
+    /// # Arguments
+    /// * `text`: the pattern
+    pub fn load_life106(&mut self, text: &str) -> Result<(), PatternError> {
+        decode(text, |_, _| {})?;
+        let h_size = self.horizontal_size as i32;
+        let v_size = self.vertical_size as i32;
+        decode(text, |x, y| {
+            let h = x.rem_euclid(h_size) as u8;
+            let v = y.rem_euclid(v_size) as u8;
+            self.set_cellstate(h, v, CellState::Alive);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn life106_load() {
+        let mut g = Grid::new(6, 5);
+        // glider around the origin
+        let glider = "#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+        g.load_life106(glider).unwrap();
+        assert_eq!(g.population(), 5);
+        for hv in [(0, 4), (1, 0), (5, 1), (0, 1), (1, 1)] {
+            assert_eq!(g.get_cellstate_hv(hv), &CellState::Alive);
+        }
+
+        // large coordinates wrap as well
+        let mut g = Grid::new(6, 5);
+        g.load_life106("#Life 1.06\n  7   -6 \n").unwrap();
+        assert_eq!(g.population(), 1);
+        assert_eq!(g.get_cellstate(1, 4), &CellState::Alive);
+    }
+
+    #[test]
+    fn life106_load_errors() {
+        let mut g = Grid::new(6, 5);
+        assert_eq!(
+            g.load_life106("#Life 1.06\n1 2\n3\n"),
+            Err(PatternError::InvalidCoordinate)
+        );
+        assert_eq!(
+            g.load_life106("1 2\n3 x\n"),
+            Err(PatternError::InvalidCoordinate)
+        );
+        // exactly two coordinates per line
+        assert_eq!(
+            g.load_life106("1 2 3\n4\n"),
+            Err(PatternError::InvalidCoordinate)
+        );
+        assert_eq!(
+            g.load_life106("1\n2\n"),
+            Err(PatternError::InvalidCoordinate)
+        );
+        // invalid patterns leave the grid untouched
+        assert_eq!(g.population(), 0);
+    }
+}
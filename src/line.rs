@@ -0,0 +1,230 @@
+//! A minimal one-dimensional grid for elementary cellular automata.
+use crate::{CellState, HORIZONTAL_MAX};
+#[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+use alloc::string::String;
+
+/// A single row of `W` cells for one-dimensional (elementary)
+/// cellular automata. The row is of ring shape, i.e. the left
+/// neighbour of the first cell is the last cell and vice versa.
+///
+/// # Remarks
+/// The width is checked at compile time: it has to be at least
+/// one cell and must not exceed the horizontal size of a `Grid`.
+#[derive(Copy, Clone, Debug)]
+pub struct Line<const W: usize> {
+    /// The current cell states.
+    cells: [CellState; W],
+    /// Temporary internal buffer to calculate the new state.
+    #[cfg_attr(not(feature = "dead-alive-only"), allow(dead_code))]
+    shadow: [CellState; W],
+}
+
+impl<const W: usize> Line<W> {
+    /// Fails the build if the width is out of range.
+    const WIDTH_CHECK: () = assert!(W > 0 && W <= HORIZONTAL_MAX, "invalid line width");
+
+    /// Create a new line filled with default (dead) cells.
+    pub fn new() -> Line<W> {
+        let () = Self::WIDTH_CHECK;
+        Line {
+            cells: [CellState::default(); W],
+            shadow: [CellState::default(); W],
+        }
+    }
+
+    /// Get the number of cells (i.e. horizontal size)
+    pub fn width(&self) -> u8 {
+        W as u8
+    }
+
+    /// Retrieve a cell state.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    pub fn get(&self, h: u8) -> &CellState {
+        if h as usize >= W {
+            panic!("horizontal coordinate too large")
+        }
+        &self.cells[h as usize]
+    }
+
+    /// Set a cell state.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `state`: the new state of the cell
+    pub fn set(&mut self, h: u8, state: CellState) {
+        if h as usize >= W {
+            panic!("horizontal coordinate too large")
+        }
+        self.cells[h as usize] = state;
+    }
+
+    /// Get the coordinate of the left neighbour,
+    /// wrapping around at the start of the line.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    pub fn left(&self, h: u8) -> u8 {
+        if h as usize >= W {
+            panic!("horizontal coordinate too large")
+        }
+        if h == 0 {
+            return (W - 1) as u8;
+        }
+        h - 1
+    }
+
+    /// Get the coordinate of the right neighbour,
+    /// wrapping around at the end of the line.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    pub fn right(&self, h: u8) -> u8 {
+        if h as usize >= W {
+            panic!("horizontal coordinate too large")
+        }
+        if h as usize == W - 1 {
+            return 0;
+        }
+        h + 1
+    }
+
+    /// Apply an elementary (Wolfram) rule to every cell.
+    /// The neighbourhood (left, center, right) is read as a
+    /// three bit number (alive = 1) which selects the bit
+    /// of the rule number holding the new state.
+    ///
+    /// # Arguments
+    /// * `rule`: the number of the elementary rule, e.g. 30
+    #[cfg(feature = "dead-alive-only")]
+    pub fn step(&mut self, rule: u8) {
+        for h in 0..W as u8 {
+            let mut index = 0u8;
+            for cs in [self.get(self.left(h)), self.get(h), self.get(self.right(h))] {
+                index <<= 1;
+                if cs == &CellState::Alive {
+                    index |= 1;
+                }
+            }
+            self.shadow[h as usize] = if (rule >> index) & 1 == 1 {
+                CellState::Alive
+            } else {
+                CellState::Dead
+            };
+        }
+        core::mem::swap(&mut self.cells, &mut self.shadow);
+    }
+
+    /// Render the line as a string, alive cells as `O`
+    /// and dead cells as `.`.
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    pub fn as_ring_string(&self) -> String {
+        self.cells
+            .iter()
+            .map(|cs| match cs {
+                CellState::Alive => 'O',
+                CellState::Dead => '.',
+            })
+            .collect()
+    }
+
+    /// Render successive generations below each other (one line
+    /// per generation, oldest first), like a scrolling window.
+    /// The line is stepped forward by `generations` updates.
+    ///
+    /// # Arguments
+    /// * `rule`: the number of the elementary rule, e.g. 30
+    /// * `generations`: number of updates to run after the current generation
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    pub fn render_generations(&mut self, rule: u8, generations: u32) -> String {
+        let mut rendered = self.as_ring_string();
+        rendered.push('\n');
+        for _ in 0..generations {
+            self.step(rule);
+            rendered.push_str(&self.as_ring_string());
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+
+impl<const W: usize> Default for Line<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "dead-alive-only")]
+    use crate::test_rules::rule30;
+    #[cfg(feature = "dead-alive-only")]
+    use crate::Universe;
+
+    #[test]
+    fn line_new() {
+        let l = Line::<5>::new();
+        assert_eq!(l.width(), 5);
+        #[cfg(feature = "dead-alive-only")]
+        for h in 0..5u8 {
+            assert_eq!(l.get(h), &CellState::Dead);
+        }
+    }
+
+    #[test]
+    fn line_left_right() {
+        let l = Line::<4>::new();
+        assert_eq!(l.left(2), 1);
+        assert_eq!(l.left(0), 3);
+        assert_eq!(l.right(2), 3);
+        assert_eq!(l.right(3), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn line_get_too_large() {
+        let l = Line::<4>::new();
+        let _ = l.get(4);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn line_set() {
+        let mut l = Line::<4>::new();
+        l.set(2, CellState::Alive);
+        assert_eq!(l.get(2), &CellState::Alive);
+        assert_eq!(l.get(1), &CellState::Dead);
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "dead-alive-only"))]
+    fn line_as_ring_string() {
+        let mut l = Line::<7>::new();
+        l.set(3, CellState::Alive);
+        assert_eq!(l.as_ring_string(), "...O...");
+
+        let rendered = l.render_generations(30, 2);
+        assert_eq!(rendered, "...O...\n..OOO..\n.OO..O.\n");
+        assert_eq!(l.as_ring_string(), ".OO..O.");
+    }
+
+    // compare against the two-dimensional implementation
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn line_step_rule30() {
+        let mut l = Line::<11>::new();
+        l.set(5, CellState::Alive);
+        let mut u = Universe::new(11, 1, rule30);
+        u.grid.set_cellstate(5, 0, CellState::Alive);
+
+        for _ in 0..8 {
+            l.step(30);
+            u.update();
+            for h in 0..11u8 {
+                assert_eq!(l.get(h), u.grid.get_cellstate(h, 0));
+            }
+        }
+    }
+}
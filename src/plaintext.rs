@@ -0,0 +1,115 @@
+//! Import of patterns in the plaintext (.cells) format,
+//! one row per line with `.` for dead and `O` for living cells.
+use crate::{CellState, Grid, PatternError};
+
+/// Decode a plaintext pattern and hand every cell
+/// (horizontal, vertical position and state) to `place`.
+///
+/// # Arguments
+/// * `text`: the pattern
+/// * `h_size`: horizontal size available for the pattern
+/// * `v_size`: vertical size available for the pattern
+/// * `place`: called for every decoded cell
+fn decode<F: FnMut(u32, u32, CellState)>(
+    text: &str,
+    h_size: u32,
+    v_size: u32,
+    mut place: F,
+) -> Result<(), PatternError> {
+    let rows = text.lines().filter(|l| !l.starts_with('!'));
+    for (y, row) in rows.enumerate() {
+        for (x, c) in row.trim_end().chars().enumerate() {
+            let state = match c {
+                '.' => CellState::Dead,
+                'O' => CellState::Alive,
+                _ => return Err(PatternError::InvalidTag(c)),
+            };
+            if x as u32 >= h_size || y as u32 >= v_size {
+                return Err(PatternError::TooLarge);
+            }
+            place(x as u32, y as u32, state);
+        }
+    }
+    Ok(())
+}
+
+impl Grid {
+    /// Load a pattern in the plaintext format into the grid. Every
+    /// line holds a row, `.` marks a dead and `O` a living cell.
+    /// Rows may be shorter than others, lines starting with `!` are
+    /// comments. The pattern is placed starting at the offset,
+    /// wrapping around the edges of the grid.
+    /// The grid is left untouched if the pattern is invalid.
+    ///
+    /// # Arguments
+    /// * `text`: the pattern
+    /// * `offset`: tuple (horizontal coordinate, vertical coordinate) of the top left corner
+    ///
+    /// # Panics
+    /// If the offset lies outside of the grid.
+    pub fn load_plaintext(&mut self, text: &str, offset: (u8, u8)) -> Result<(), PatternError> {
+        if offset.0 >= self.horizontal_size {
+            panic!("horizontal coordinate too large")
+        }
+        if offset.1 >= self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        let h_size = self.horizontal_size as u32;
+        let v_size = self.vertical_size as u32;
+        decode(text, h_size, v_size, |_, _, _| {})?;
+        decode(text, h_size, v_size, |x, y, state| {
+            let h = (offset.0 as u32 + x) % h_size;
+            let v = (offset.1 as u32 + y) % v_size;
+            self.set_cellstate(h as u8, v as u8, state);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The glider as published on LifeWiki.
+    const GLIDER: &str = "!Name: Glider\n!\n.O\n..O\nOOO\n";
+
+    #[test]
+    fn plaintext_load_glider() {
+        let mut g = Grid::new(8, 8);
+        g.load_plaintext(GLIDER, (2, 3)).unwrap();
+        let glider = [(3, 3), (4, 4), (2, 5), (3, 5), (4, 5)];
+        for (h, v, cs) in g.iter_cells() {
+            if glider.contains(&(h, v)) {
+                assert_eq!(cs, &CellState::Alive);
+            } else {
+                assert_eq!(cs, &CellState::Dead);
+            }
+        }
+
+        // wrapping around the edges
+        let mut g = Grid::new(4, 4);
+        g.load_plaintext(GLIDER, (3, 3)).unwrap();
+        assert_eq!(g.population(), 5);
+        for hv in [(0, 3), (1, 0), (3, 1), (0, 1), (1, 1)] {
+            assert_eq!(g.get_cellstate_hv(hv), &CellState::Alive);
+        }
+    }
+
+    #[test]
+    fn plaintext_load_errors() {
+        let mut g = Grid::new(3, 3);
+        assert_eq!(
+            g.load_plaintext(".O\nxO", (0, 0)),
+            Err(PatternError::InvalidTag('x'))
+        );
+        assert_eq!(
+            g.load_plaintext("OOOO", (0, 0)),
+            Err(PatternError::TooLarge)
+        );
+        assert_eq!(
+            g.load_plaintext("O\n\n\nO", (0, 0)),
+            Err(PatternError::TooLarge)
+        );
+        // invalid patterns leave the grid untouched
+        assert_eq!(g.population(), 0);
+    }
+}
@@ -0,0 +1,357 @@
+//! Import and export of patterns in the RLE (run length encoded)
+//! format used by the Game of Life community, e.g. on LifeWiki.
+use crate::{CellState, Grid, PatternError};
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String};
+
+/// Maximum length of a line of an exported pattern.
+#[cfg(feature = "alloc")]
+const LINE_LENGTH: usize = 70;
+
+/// Parse the value of a header entry such as `x = 3`.
+///
+/// # Arguments
+/// * `entry`: the entry of the header line
+/// * `key`: the expected key
+fn header_value(entry: Option<&str>, key: &str) -> Result<u32, PatternError> {
+    let (k, value) = entry
+        .and_then(|e| e.split_once('='))
+        .ok_or(PatternError::InvalidHeader)?;
+    if k.trim() != key {
+        return Err(PatternError::InvalidHeader);
+    }
+    value
+        .trim()
+        .parse()
+        .map_err(|_| PatternError::InvalidHeader)
+}
+
+/// Decode the runs of an RLE pattern and hand every
+/// cell (horizontal, vertical position and state) to `place`.
+///
+/// # Arguments
+/// * `rle`: the pattern
+/// * `h_size`: horizontal size available for the pattern
+/// * `v_size`: vertical size available for the pattern
+/// * `place`: called for every decoded cell
+fn decode<F: FnMut(u32, u32, CellState)>(
+    rle: &str,
+    h_size: u32,
+    v_size: u32,
+    mut place: F,
+) -> Result<(), PatternError> {
+    let mut lines = rle
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .peekable();
+    if let Some(header) = lines.next_if(|l| l.starts_with('x')) {
+        let mut entries = header.split(',');
+        let width = header_value(entries.next(), "x")?;
+        let height = header_value(entries.next(), "y")?;
+        if width > h_size || height > v_size {
+            return Err(PatternError::TooLarge);
+        }
+    }
+
+    let (mut x, mut y) = (0u32, 0u32);
+    let mut count: Option<u32> = None;
+    for c in lines.flat_map(str::chars) {
+        match c {
+            '0'..='9' => {
+                let digit = c as u32 - '0' as u32;
+                count = Some(
+                    count
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(digit))
+                        .ok_or(PatternError::InvalidCount)?,
+                );
+            }
+            'b' | 'o' => {
+                let run = count.take().unwrap_or(1);
+                let end = x.checked_add(run).ok_or(PatternError::TooLarge)?;
+                if end > h_size || y >= v_size {
+                    return Err(PatternError::TooLarge);
+                }
+                let state = if c == 'o' {
+                    CellState::Alive
+                } else {
+                    CellState::Dead
+                };
+                for i in 0..run {
+                    place(x + i, y, state);
+                }
+                x = end;
+            }
+            '$' => {
+                y = y
+                    .checked_add(count.take().unwrap_or(1))
+                    .ok_or(PatternError::TooLarge)?;
+                x = 0;
+            }
+            '!' => break,
+            c if c.is_whitespace() => {}
+            _ => return Err(PatternError::InvalidTag(c)),
+        }
+    }
+    Ok(())
+}
+
+impl Grid {
+    /// Load a pattern in the RLE format (e.g. from LifeWiki) into the
+    /// grid. Runs of dead (`b`) and living (`o`) cells are placed
+    /// starting at the offset, wrapping around the edges of the grid.
+    /// Lines starting with `#` are comments, an optional header line
+    /// (`x = .., y = ..`) gives the size of the pattern.
+    /// The grid is left untouched if the pattern is invalid.
+    ///
+    /// # Arguments
+    /// * `rle`: the pattern
+    /// * `offset`: tuple (horizontal coordinate, vertical coordinate) of the top left corner
+    ///
+    /// # Panics
+    /// If the offset lies outside of the grid.
+    pub fn load_rle(&mut self, rle: &str, offset: (u8, u8)) -> Result<(), PatternError> {
+        if offset.0 >= self.horizontal_size {
+            panic!("horizontal coordinate too large")
+        }
+        if offset.1 >= self.vertical_size {
+            panic!("vertical coordinate too large")
+        }
+        let h_size = self.horizontal_size as u32;
+        let v_size = self.vertical_size as u32;
+        decode(rle, h_size, v_size, |_, _, _| {})?;
+        decode(rle, h_size, v_size, |x, y, state| {
+            let h = (offset.0 as u32 + x) % h_size;
+            let v = (offset.1 as u32 + y) % v_size;
+            self.set_cellstate(h as u8, v as u8, state);
+        })
+    }
+
+    /// Export the grid in the RLE format, starting with a header line
+    /// holding the grid size. Dead cells at the end of a row and empty
+    /// rows at the bottom are left out. Lines are at most 70 characters.
+    #[cfg(feature = "alloc")]
+    pub fn to_rle(&self) -> String {
+        self.encode_rle((0, 0), (self.horizontal_size - 1, self.vertical_size - 1))
+    }
+
+    /// Export only the smallest rectangle holding all living cells
+    /// (see `live_bounding_box()`) in the RLE format, so the header
+    /// holds the size of the pattern instead of the grid. A pattern
+    /// is exported the same regardless of its position on the grid.
+    /// An empty grid results in an empty pattern of size 0x0.
+    #[cfg(feature = "alloc")]
+    pub fn to_rle_cropped(&self) -> String {
+        match self.live_bounding_box() {
+            Some((top_left, bottom_right)) => self.encode_rle(top_left, bottom_right),
+            None => String::from("x = 0, y = 0\n!\n"),
+        }
+    }
+
+    /// Export a region of the grid in the RLE format. All living
+    /// cells of the rows inside the region have to lie in it.
+    ///
+    /// # Arguments
+    /// * `top_left`: tuple (horizontal coordinate, vertical coordinate) of the top left corner
+    /// * `bottom_right`: tuple (horizontal coordinate, vertical coordinate) of the bottom right corner
+    #[cfg(feature = "alloc")]
+    fn encode_rle(&self, top_left: (u8, u8), bottom_right: (u8, u8)) -> String {
+        let (left, top) = top_left;
+        let (right, bottom) = bottom_right;
+        let mut rle = format!(
+            "x = {}, y = {}\n",
+            right as u32 - left as u32 + 1,
+            bottom as u32 - top as u32 + 1
+        );
+        let mut line = String::new();
+        let mut push = |count: u32, tag: char| {
+            let token = if count == 1 {
+                format!("{}", tag)
+            } else {
+                format!("{}{}", count, tag)
+            };
+            if line.len() + token.len() > LINE_LENGTH {
+                rle.push_str(&line);
+                rle.push('\n');
+                line.clear();
+            }
+            line.push_str(&token);
+        };
+
+        // row of the last written cells
+        let mut y = top;
+        for v in top..=bottom {
+            if self.count_alive_in_row(v) == 0 {
+                continue;
+            }
+            if v > y {
+                push((v - y) as u32, '$');
+                y = v;
+            }
+
+            let mut run: Option<(CellState, u32)> = None;
+            for h in left..=right {
+                let state = *self.get_cellstate(h, v);
+                run = match run {
+                    Some((s, n)) if s == state => Some((s, n + 1)),
+                    Some((s, n)) => {
+                        push(n, if s == CellState::Alive { 'o' } else { 'b' });
+                        Some((state, 1))
+                    }
+                    None => Some((state, 1)),
+                };
+            }
+            // trailing dead cells are left out
+            if let Some((CellState::Alive, n)) = run {
+                push(n, 'o');
+            }
+        }
+        push(1, '!');
+        rle.push_str(&line);
+        rle.push('\n');
+        rle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The glider as published on LifeWiki.
+    const GLIDER: &str =
+        "#N Glider\n#C The smallest spaceship.\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n";
+
+    #[test]
+    fn rle_load_glider() {
+        let mut g = Grid::new(8, 8);
+        g.load_rle(GLIDER, (2, 3)).unwrap();
+        let glider = [(3, 3), (4, 4), (2, 5), (3, 5), (4, 5)];
+        for (h, v, cs) in g.iter_cells() {
+            if glider.contains(&(h, v)) {
+                assert_eq!(cs, &CellState::Alive);
+            } else {
+                assert_eq!(cs, &CellState::Dead);
+            }
+        }
+    }
+
+    #[test]
+    fn rle_load_wrapping() {
+        let mut g = Grid::new(4, 4);
+        g.load_rle(GLIDER, (3, 3)).unwrap();
+        assert_eq!(g.population(), 5);
+        for hv in [(0, 3), (1, 0), (3, 1), (0, 1), (1, 1)] {
+            assert_eq!(g.get_cellstate_hv(hv), &CellState::Alive);
+        }
+    }
+
+    #[test]
+    fn rle_load_runs() {
+        // no header, runs spanning lines
+        let mut g = Grid::new(6, 4);
+        g.load_rle("3o$\n2$\n2b4o!", (0, 0)).unwrap();
+        assert_eq!(g.population(), 7);
+        assert_eq!(g.count_alive_in_row(0), 3);
+        assert_eq!(g.count_alive_in_row(3), 4);
+        assert_eq!(g.get_cellstate(1, 3), &CellState::Dead);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn rle_to_rle() {
+        let mut g = Grid::new(3, 3);
+        g.load_rle(GLIDER, (0, 0)).unwrap();
+        assert_eq!(g.to_rle(), "x = 3, y = 3\nbo$2bo$3o!\n");
+
+        // empty rows at the top and in between
+        let mut g = Grid::new(5, 6);
+        g.set_cellstate(4, 2, CellState::Alive);
+        g.set_cellstate(0, 5, CellState::Alive);
+        g.set_cellstate(1, 5, CellState::Alive);
+        assert_eq!(g.to_rle(), "x = 5, y = 6\n2$4bo3$2o!\n");
+
+        assert_eq!(Grid::new(2, 2).to_rle(), "x = 2, y = 2\n!\n");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn rle_to_rle_cropped() {
+        for offset in [(0, 0), (30, 17), (61, 61), (62, 40)] {
+            let mut g = Grid::new(64, 64);
+            g.load_rle(GLIDER, offset).unwrap();
+            if offset.0 + 3 <= 64 && offset.1 + 3 <= 64 {
+                assert_eq!(g.to_rle_cropped(), "x = 3, y = 3\nbo$2bo$3o!\n");
+            } else {
+                // the glider wraps around the edge
+                assert!(g.to_rle_cropped().starts_with("x = 64, y = 3\n"));
+            }
+        }
+
+        // empty rows inside the bounding box are kept
+        let mut g = Grid::new(20, 20);
+        g.set_cellstate(5, 4, CellState::Alive);
+        g.set_cellstate(7, 7, CellState::Alive);
+        assert_eq!(g.to_rle_cropped(), "x = 3, y = 4\no3$2bo!\n");
+
+        assert_eq!(Grid::new(5, 5).to_rle_cropped(), "x = 0, y = 0\n!\n");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn rle_roundtrip() {
+        let mut g = Grid::new(200, 7);
+        for h in (0..200).step_by(3) {
+            g.set_cellstate(h, h % 7, CellState::Alive);
+            g.set_cellstate(h, 6 - h % 7, CellState::Alive);
+        }
+        let rle = g.to_rle();
+        assert!(rle.lines().all(|l| l.len() <= 70));
+
+        let mut restored = Grid::new(200, 7);
+        restored.load_rle(&rle, (0, 0)).unwrap();
+        assert_eq!(restored, g);
+    }
+
+    #[test]
+    fn rle_load_errors() {
+        let mut g = Grid::new(4, 4);
+        assert_eq!(
+            g.load_rle("bxo!", (0, 0)),
+            Err(PatternError::InvalidTag('x'))
+        );
+        assert_eq!(g.load_rle("5o!", (0, 0)), Err(PatternError::TooLarge));
+        assert_eq!(g.load_rle("o4$o!", (0, 0)), Err(PatternError::TooLarge));
+        assert_eq!(
+            g.load_rle("x = 5, y = 1\no!", (0, 0)),
+            Err(PatternError::TooLarge)
+        );
+        assert_eq!(
+            g.load_rle("x = a, y = 1\no!", (0, 0)),
+            Err(PatternError::InvalidHeader)
+        );
+        assert_eq!(
+            g.load_rle("99999999999o!", (0, 0)),
+            Err(PatternError::InvalidCount)
+        );
+        // runs adding up beyond the largest count
+        assert_eq!(
+            g.load_rle("o4294967295o!", (0, 0)),
+            Err(PatternError::TooLarge)
+        );
+        assert_eq!(
+            g.load_rle("4294967295$4294967295$o!", (0, 0)),
+            Err(PatternError::TooLarge)
+        );
+        // nothing was placed
+        assert_eq!(g.population(), 0);
+        assert_eq!(g.load_rle("o$oo$3o!", (0, 0)), Ok(()));
+        // invalid patterns leave the grid untouched
+        assert_eq!(
+            g.load_rle("3o$2o$x!", (0, 0)),
+            Err(PatternError::InvalidTag('x'))
+        );
+        assert_eq!(g.population(), 6);
+    }
+}
@@ -0,0 +1,557 @@
+//! Ready-made rules and helpers to combine rules.
+#[cfg(feature = "dead-alive-only")]
+use crate::Direction;
+use crate::{CellState, Grid};
+
+/// Combine two rules by splitting the grid horizontally.
+/// Cells above the boundary row are handled by `top`,
+/// the boundary row and everything below by `bottom`.
+///
+/// # Arguments
+/// * `boundary_v`: vertical coordinate of the first row handled by `bottom`
+/// * `top`: rule for the rows above the boundary
+/// * `bottom`: rule for the remaining rows
+pub fn spatial_split(
+    boundary_v: u8,
+    top: fn(u8, u8, &Grid) -> CellState,
+    bottom: fn(u8, u8, &Grid) -> CellState,
+) -> impl Fn(u8, u8, &Grid) -> CellState {
+    move |h, v, g| {
+        if v < boundary_v {
+            top(h, v, g)
+        } else {
+            bottom(h, v, g)
+        }
+    }
+}
+
+/// Check if two rules calculate the same new state for every
+/// cell of a sample grid, e.g. to verify an optimized rule
+/// against a reference implementation.
+///
+/// # Arguments
+/// * `a`: the first rule
+/// * `b`: the second rule
+/// * `g`: the sample grid
+pub fn rules_agree(
+    a: fn(u8, u8, &Grid) -> CellState,
+    b: fn(u8, u8, &Grid) -> CellState,
+    g: &Grid,
+) -> bool {
+    g.iter_cells().all(|(h, v, _)| a(h, v, g) == b(h, v, g))
+}
+
+/// Conway's Game of Life (B3/S23) on the Moore neighbourhood.
+/// A dead cell with exactly three living neighbours is born,
+/// a living cell with two or three living neighbours survives.
+///
+/// # Arguments
+/// * `h`: horizontal coordinate
+/// * `v`: vertical coordinate
+/// * `g`: the current grid
+#[cfg(feature = "conway")]
+pub fn game_of_life(h: u8, v: u8, g: &Grid) -> CellState {
+    match (g.get_cellstate(h, v), g.count_live_neighbours(h, v)) {
+        (CellState::Alive, 2) | (_, 3) => CellState::Alive,
+        _ => CellState::Dead,
+    }
+}
+
+/// Build a Life-like rule on the Moore neighbourhood from the
+/// numbers of living neighbours for which a dead cell is born
+/// and a living cell survives, e.g. `life_like(&[3, 6], &[2, 3])`
+/// for HighLife (B36/S23). All other cells die or stay dead.
+///
+/// # Arguments
+/// * `birth`: neighbour counts bringing a dead cell to life
+/// * `survival`: neighbour counts keeping a living cell alive
+///
+/// # Panics
+/// If a neighbour count is larger than 8.
+#[cfg(feature = "dead-alive-only")]
+pub fn life_like(birth: &[u8], survival: &[u8]) -> impl Fn(u8, u8, &Grid) -> CellState + Copy {
+    let birth = count_mask(birth);
+    let survival = count_mask(survival);
+    move |h, v, g| {
+        life_like_state(
+            birth,
+            survival,
+            *g.get_cellstate(h, v),
+            g.count_live_neighbours(h, v),
+        )
+    }
+}
+
+/// Calculate the new state of a cell under a Life-like rule.
+///
+/// # Arguments
+/// * `birth`: mask of the neighbour counts bringing a dead cell to life (see `count_mask()`)
+/// * `survival`: mask of the neighbour counts keeping a living cell alive
+/// * `state`: the current state of the cell
+/// * `neighbours`: the number of living neighbours
+#[cfg(feature = "dead-alive-only")]
+pub(crate) fn life_like_state(
+    birth: u16,
+    survival: u16,
+    state: CellState,
+    neighbours: u8,
+) -> CellState {
+    let counts = match state {
+        CellState::Alive => survival,
+        CellState::Dead => birth,
+    };
+    if counts & 1 << neighbours != 0 {
+        CellState::Alive
+    } else {
+        CellState::Dead
+    }
+}
+
+/// Turn neighbour counts into a bit mask (bit n set = count n listed).
+///
+/// # Arguments
+/// * `counts`: the neighbour counts
+///
+/// # Panics
+/// If a neighbour count is larger than 8.
+#[cfg(feature = "dead-alive-only")]
+pub(crate) fn count_mask(counts: &[u8]) -> u16 {
+    counts.iter().fold(0u16, |mask, &count| {
+        if count > 8 {
+            panic!("neighbour count too large")
+        }
+        mask | 1 << count
+    })
+}
+
+/// An elementary (Wolfram) rule on the row of each cell. The
+/// neighbourhood (west, center, east) is read as a three bit number
+/// (alive = 1) which selects the bit of `RULE` holding the new state.
+/// Cells beyond a fixed boundary count as dead.
+///
+/// # Arguments
+/// * `h`: horizontal coordinate
+/// * `v`: vertical coordinate
+/// * `g`: the current grid
+#[cfg(feature = "dead-alive-only")]
+fn elementary<const RULE: u8>(h: u8, v: u8, g: &Grid) -> CellState {
+    let alive = |hv: Option<(u8, u8)>| match hv {
+        Some(hv) => (g.get_cellstate_hv(hv) == &CellState::Alive) as u8,
+        None => 0,
+    };
+    let index = alive(g.neighbour(h, v, Direction::West)) << 2
+        | alive(Some((h, v))) << 1
+        | alive(g.neighbour(h, v, Direction::East));
+    if (RULE >> index) & 1 == 1 {
+        CellState::Alive
+    } else {
+        CellState::Dead
+    }
+}
+
+/// Build a table of all elementary rules, indexed by rule number.
+#[cfg(feature = "dead-alive-only")]
+macro_rules! elementary_table {
+    ($($n:literal)*) => {
+        [$(elementary::<$n> as fn(u8, u8, &Grid) -> CellState),*]
+    };
+}
+
+/// All 256 elementary rules, indexed by rule number.
+#[cfg(feature = "dead-alive-only")]
+static ELEMENTARY_RULES: [fn(u8, u8, &Grid) -> CellState; 256] = elementary_table!(
+    0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15
+    16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+    32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47
+    48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63
+    64 65 66 67 68 69 70 71 72 73 74 75 76 77 78 79
+    80 81 82 83 84 85 86 87 88 89 90 91 92 93 94 95
+    96 97 98 99 100 101 102 103 104 105 106 107 108 109 110 111
+    112 113 114 115 116 117 118 119 120 121 122 123 124 125 126 127
+    128 129 130 131 132 133 134 135 136 137 138 139 140 141 142 143
+    144 145 146 147 148 149 150 151 152 153 154 155 156 157 158 159
+    160 161 162 163 164 165 166 167 168 169 170 171 172 173 174 175
+    176 177 178 179 180 181 182 183 184 185 186 187 188 189 190 191
+    192 193 194 195 196 197 198 199 200 201 202 203 204 205 206 207
+    208 209 210 211 212 213 214 215 216 217 218 219 220 221 222 223
+    224 225 226 227 228 229 230 231 232 233 234 235 236 237 238 239
+    240 241 242 243 244 245 246 247 248 249 250 251 252 253 254 255
+);
+
+/// Get the elementary (Wolfram) rule with the given number, e.g. 30,
+/// 90 or 110. Each cell is updated based on itself and its western
+/// and eastern neighbour. Cells beyond a fixed boundary count as dead.
+///
+/// # Arguments
+/// * `rule_number`: the number of the elementary rule
+#[cfg(feature = "dead-alive-only")]
+pub fn elementary_rule(rule_number: u8) -> fn(u8, u8, &Grid) -> CellState {
+    ELEMENTARY_RULES[rule_number as usize]
+}
+
+/// Brian's Brain, a three state automaton on the Moore
+/// neighbourhood. The states are `CellState(0)` (off),
+/// `CellState(1)` (on) and `CellState(2)` (dying). An off cell
+/// turns on if exactly two neighbours are on, an on cell starts
+/// dying and a dying cell turns off. Other states count as off.
+///
+/// # Arguments
+/// * `h`: horizontal coordinate
+/// * `v`: vertical coordinate
+/// * `g`: the current grid
+#[cfg(feature = "multistate")]
+pub fn brians_brain(h: u8, v: u8, g: &Grid) -> CellState {
+    match *g.get_cellstate(h, v) {
+        CellState(1) => CellState(2),
+        CellState(2) => CellState(0),
+        _ => {
+            if g.count_neighbours_matching(h, v, |cs| cs == &CellState(1)) == 2 {
+                CellState(1)
+            } else {
+                CellState(0)
+            }
+        }
+    }
+}
+
+/// WireWorld on the Moore neighbourhood. Empty cells stay empty,
+/// an electron head turns into a tail, a tail turns back into a
+/// conductor and a conductor turns into a head if exactly one or
+/// two neighbours are electron heads.
+///
+/// # Arguments
+/// * `h`: horizontal coordinate
+/// * `v`: vertical coordinate
+/// * `g`: the current grid
+#[cfg(feature = "wireworld")]
+pub fn wireworld(h: u8, v: u8, g: &Grid) -> CellState {
+    match *g.get_cellstate(h, v) {
+        CellState::Empty => CellState::Empty,
+        CellState::ElectronHead => CellState::ElectronTail,
+        CellState::ElectronTail => CellState::Conductor,
+        CellState::Conductor => {
+            match g.count_neighbours_matching(h, v, |cs| cs == &CellState::ElectronHead) {
+                1 | 2 => CellState::ElectronHead,
+                _ => CellState::Conductor,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(
+        feature = "dead-alive-only",
+        feature = "multistate",
+        feature = "wireworld"
+    ))]
+    use super::*;
+    #[cfg(feature = "dead-alive-only")]
+    use crate::test_rules::rule30;
+    #[cfg(any(
+        feature = "dead-alive-only",
+        feature = "multistate",
+        feature = "wireworld"
+    ))]
+    use crate::Universe;
+
+    /// Check that exactly the given cells are alive.
+    #[cfg(feature = "dead-alive-only")]
+    fn assert_alive(g: &Grid, alive: &[(u8, u8)]) {
+        for h in 0..g.get_horizontal_size() {
+            for v in 0..g.get_vertical_size() {
+                let expected = if alive.contains(&(h, v)) {
+                    CellState::Alive
+                } else {
+                    CellState::Dead
+                };
+                assert_eq!(g.get_cellstate(h, v), &expected, "cell ({}, {})", h, v);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "conway")]
+    fn rules_game_of_life_blinker() {
+        let mut u = Universe::new(5, 5, game_of_life);
+        let horizontal = [(1, 2), (2, 2), (3, 2)];
+        let vertical = [(2, 1), (2, 2), (2, 3)];
+        for hv in horizontal {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+        u.update();
+        assert_alive(&u.grid, &vertical);
+        u.update();
+        assert_alive(&u.grid, &horizontal);
+    }
+
+    #[test]
+    #[cfg(feature = "conway")]
+    fn rules_game_of_life_block() {
+        let mut u = Universe::new(4, 4, game_of_life);
+        let block = [(1, 1), (2, 1), (1, 2), (2, 2)];
+        for hv in block {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+        u.update();
+        assert_alive(&u.grid, &block);
+    }
+
+    #[test]
+    #[cfg(feature = "conway")]
+    fn rules_game_of_life_glider() {
+        let mut u = Universe::new(6, 6, game_of_life);
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        for hv in glider {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+        u.update();
+        assert_alive(&u.grid, &[(0, 1), (2, 1), (1, 2), (2, 2), (1, 3)]);
+        // after four generations the glider moved one cell south east
+        for _ in 0..3 {
+            u.update();
+        }
+        let moved = glider.map(|(h, v)| (h + 1, v + 1));
+        assert_alive(&u.grid, &moved);
+    }
+
+    #[test]
+    #[cfg(feature = "conway")]
+    fn rules_agree_game_of_life() {
+        fn b3s23(h: u8, v: u8, g: &Grid) -> CellState {
+            let alive = g.get_cellstate(h, v) == &CellState::Alive;
+            match g.count_live_neighbours(h, v) {
+                3 => CellState::Alive,
+                2 if alive => CellState::Alive,
+                _ => CellState::Dead,
+            }
+        }
+        fn b3s2(h: u8, v: u8, g: &Grid) -> CellState {
+            let alive = g.get_cellstate(h, v) == &CellState::Alive;
+            match g.count_live_neighbours(h, v) {
+                3 if !alive => CellState::Alive,
+                2 if alive => CellState::Alive,
+                _ => CellState::Dead,
+            }
+        }
+
+        let mut g = Grid::new(32, 24);
+        g.randomize(5, 80);
+        assert!(rules_agree(game_of_life, b3s23, &g));
+        assert!(!rules_agree(game_of_life, b3s2, &g));
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn rules_life_like_conway_blinker() {
+        let mut u = Universe::new_with_closure(5, 5, life_like(&[3], &[2, 3]));
+        let horizontal = [(1, 2), (2, 2), (3, 2)];
+        let vertical = [(2, 1), (2, 2), (2, 3)];
+        for hv in horizontal {
+            u.grid.set_cellstate_hv(hv, CellState::Alive);
+        }
+        u.update();
+        assert_alive(&u.grid, &vertical);
+        u.update();
+        assert_alive(&u.grid, &horizontal);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn rules_life_like_highlife_replicator() {
+        let replicator = [
+            (2, 0),
+            (3, 0),
+            (4, 0),
+            (1, 1),
+            (4, 1),
+            (0, 2),
+            (4, 2),
+            (0, 3),
+            (3, 3),
+            (0, 4),
+            (1, 4),
+            (2, 4),
+        ];
+        let mut u = Universe::new_with_closure(16, 16, life_like(&[3, 6], &[2, 3]));
+        for (h, v) in replicator {
+            u.grid.set_cellstate(h + 5, v + 5, CellState::Alive);
+        }
+        // after 12 generations there are two copies (shifted diagonally)
+        for _ in 0..12 {
+            u.update();
+        }
+        let mut copies = [(0, 0); 24];
+        for (i, (h, v)) in replicator.iter().enumerate() {
+            copies[i] = (h + 3, v + 3);
+            copies[i + 12] = (h + 7, v + 7);
+        }
+        assert_alive(&u.grid, &copies);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "dead-alive-only")]
+    fn rules_life_like_count_too_large() {
+        let _ = life_like(&[3], &[9]);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn rules_spatial_split() {
+        fn identity(h: u8, v: u8, g: &Grid) -> CellState {
+            *g.get_cellstate(h, v)
+        }
+        fn inversion(h: u8, v: u8, g: &Grid) -> CellState {
+            match *g.get_cellstate(h, v) {
+                CellState::Alive => CellState::Dead,
+                CellState::Dead => CellState::Alive,
+            }
+        }
+
+        let mut u = Universe::new_with_closure(6, 6, spatial_split(3, inversion, identity));
+        u.grid.randomize(5, 128);
+        let before = u.grid;
+        u.update();
+        for h in 0..6u8 {
+            for v in 0..6u8 {
+                let old = before.get_cellstate(h, v);
+                if v < 3 {
+                    // top half is inverted
+                    assert_ne!(u.grid.get_cellstate(h, v), old);
+                } else {
+                    // bottom half stays unchanged
+                    assert_eq!(u.grid.get_cellstate(h, v), old);
+                }
+            }
+        }
+        assert_eq!(u.generation(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "multistate")]
+    fn rules_brians_brain() {
+        let mut u = Universe::new(5, 5, brians_brain);
+        u.grid.set_cellstate(1, 2, CellState(1));
+        u.grid.set_cellstate(3, 2, CellState(1));
+
+        u.update();
+        let expected = [
+            ((1, 2), 2),
+            ((3, 2), 2),
+            ((2, 1), 1),
+            ((2, 2), 1),
+            ((2, 3), 1),
+        ];
+        for (hv, state) in expected {
+            assert_eq!(u.grid.get_cellstate_hv(hv), &CellState(state));
+        }
+        assert_eq!(
+            u.grid.iter_cells().filter(|(_, _, cs)| cs.0 != 0).count(),
+            5
+        );
+
+        u.update();
+        for hv in [(2, 1), (2, 2), (2, 3)] {
+            assert_eq!(u.grid.get_cellstate_hv(hv), &CellState(2));
+        }
+        for hv in [(1, 1), (1, 3), (3, 1), (3, 3)] {
+            assert_eq!(u.grid.get_cellstate_hv(hv), &CellState(1));
+        }
+
+        // on, dying, off and on again
+        assert_eq!(u.grid.get_cellstate(1, 2), &CellState(0));
+        u.update();
+        assert_eq!(u.grid.get_cellstate(1, 2), &CellState(1));
+    }
+
+    /// Build a diode passing electrons from left to right.
+    #[cfg(feature = "wireworld")]
+    fn wireworld_diode() -> Universe {
+        let mut u = Universe::new(12, 5, wireworld);
+        for h in (1..11).filter(|h| *h != 6) {
+            u.grid.set_cellstate(h, 2, CellState::Conductor);
+        }
+        for hv in [(5, 1), (6, 1), (5, 3), (6, 3)] {
+            u.grid.set_cellstate_hv(hv, CellState::Conductor);
+        }
+        u
+    }
+
+    #[test]
+    #[cfg(feature = "wireworld")]
+    fn rules_wireworld_diode_forward() {
+        let mut u = wireworld_diode();
+        u.grid.set_cellstate(1, 2, CellState::ElectronTail);
+        u.grid.set_cellstate(2, 2, CellState::ElectronHead);
+
+        u.update();
+        assert_eq!(u.grid.get_cellstate(3, 2), &CellState::ElectronHead);
+        assert_eq!(u.grid.get_cellstate(2, 2), &CellState::ElectronTail);
+        assert_eq!(u.grid.get_cellstate(1, 2), &CellState::Conductor);
+        for _ in 0..7 {
+            u.update();
+        }
+        // the electron passed the diode
+        assert_eq!(u.grid.get_cellstate(10, 2), &CellState::ElectronHead);
+        assert_eq!(u.grid.get_cellstate(9, 2), &CellState::ElectronTail);
+        // the gap stays empty
+        assert_eq!(u.grid.get_cellstate(6, 2), &CellState::Empty);
+    }
+
+    #[test]
+    #[cfg(feature = "wireworld")]
+    fn rules_wireworld_diode_backward() {
+        let mut u = wireworld_diode();
+        u.grid.set_cellstate(10, 2, CellState::ElectronTail);
+        u.grid.set_cellstate(9, 2, CellState::ElectronHead);
+
+        for _ in 0..12 {
+            u.update();
+            // the electron never reaches the left wire
+            for h in 1..5 {
+                assert_eq!(u.grid.get_cellstate(h, 2), &CellState::Conductor);
+            }
+        }
+        // and is gone
+        assert!(u
+            .grid
+            .iter_cells()
+            .all(|(_, _, cs)| *cs == CellState::Empty || *cs == CellState::Conductor));
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn rules_elementary_rule30() {
+        // rule 30 as in the example
+        let mut expected = Universe::new(15, 1, rule30);
+        let mut u = Universe::new(15, 1, elementary_rule(30));
+        expected.grid.set_cellstate(7, 0, CellState::Alive);
+        u.grid.set_cellstate(7, 0, CellState::Alive);
+        for _ in 0..10 {
+            expected.update();
+            u.update();
+            assert_eq!(u.grid, expected.grid);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn rules_elementary_rule90() {
+        // Sierpinski triangle
+        let rows: [&[u8]; 5] = [&[7], &[6, 8], &[5, 9], &[4, 6, 8, 10], &[3, 11]];
+        let mut u = Universe::new(15, 1, elementary_rule(90));
+        u.grid.set_cellstate(7, 0, CellState::Alive);
+        for alive in rows {
+            for h in 0..15 {
+                let expected = if alive.contains(&h) {
+                    CellState::Alive
+                } else {
+                    CellState::Dead
+                };
+                assert_eq!(u.grid.get_cellstate(h, 0), &expected);
+            }
+            u.update();
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! (De)serialization of grids with serde. A grid is stored
+//! as its dimensions plus all cell states row by row.
+use crate::{CellState, Grid};
+use core::fmt;
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The names of the serialized fields of a grid.
+const FIELDS: &[&str] = &["horizontal_size", "vertical_size", "cells"];
+
+/// The serialized fields of a grid.
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum Field {
+    /// number of horizontal cells
+    HorizontalSize,
+    /// number of vertical cells
+    VerticalSize,
+    /// all cell states row by row
+    Cells,
+}
+
+/// The cell states of a grid, serialized row by row.
+struct Cells<'a>(&'a Grid);
+
+impl Serialize for Cells<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len = self.0.horizontal_size as usize * self.0.vertical_size as usize;
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        for (_, _, cs) in self.0.iter_cells() {
+            seq.serialize_element(cs)?;
+        }
+        seq.end()
+    }
+}
+
+/// Deserialize the cell states (row by row) directly
+/// into a grid of matching dimensions.
+struct CellsSeed<'a>(&'a mut Grid);
+
+impl<'de> DeserializeSeed<'de> for CellsSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for CellsSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} cell states",
+            self.0.horizontal_size as usize * self.0.vertical_size as usize
+        )
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let h_size = self.0.horizontal_size as usize;
+        let len = h_size * self.0.vertical_size as usize;
+        for i in 0..len {
+            let state: CellState = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            self.0
+                .set_cellstate((i % h_size) as u8, (i / h_size) as u8, state);
+        }
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(len + 1, &self));
+        }
+        Ok(())
+    }
+}
+
+/// Build a grid from serialized fields.
+struct GridVisitor;
+
+impl<'de> Visitor<'de> for GridVisitor {
+    type Value = Grid;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a grid")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Grid, A::Error> {
+        let h: u8 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let v: u8 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let mut grid = Grid::try_new(h, v).map_err(de::Error::custom)?;
+        seq.next_element_seed(CellsSeed(&mut grid))?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        Ok(grid)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Grid, A::Error> {
+        let mut h: Option<u8> = None;
+        let mut v: Option<u8> = None;
+        let mut grid: Option<Grid> = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::HorizontalSize => h = Some(map.next_value()?),
+                Field::VerticalSize => v = Some(map.next_value()?),
+                Field::Cells => {
+                    let (h, v) = match (h, v) {
+                        (Some(h), Some(v)) => (h, v),
+                        _ => {
+                            return Err(de::Error::custom(
+                                "grid dimensions have to precede the cells",
+                            ))
+                        }
+                    };
+                    let mut g = Grid::try_new(h, v).map_err(de::Error::custom)?;
+                    map.next_value_seed(CellsSeed(&mut g))?;
+                    grid = Some(g);
+                }
+            }
+        }
+        grid.ok_or_else(|| de::Error::missing_field("cells"))
+    }
+}
+
+impl Serialize for Grid {
+    /// Serialize the dimensions and the cell states (row by row).
+    ///
+    /// # Remarks
+    /// The boundary behaviour, the active mask and the
+    /// iterator positions are not serialized.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Grid", FIELDS.len())?;
+        state.serialize_field("horizontal_size", &self.horizontal_size)?;
+        state.serialize_field("vertical_size", &self.vertical_size)?;
+        state.serialize_field("cells", &Cells(self))?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Grid {
+    /// Deserialize a grid from its dimensions and cell states.
+    ///
+    /// # Remarks
+    /// The dimensions have to precede the cell states.
+    /// The grid wraps around at all edges.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Grid, D::Error> {
+        deserializer.deserialize_struct("Grid", FIELDS, GridVisitor)
+    }
+}
+
+#[cfg(all(test, feature = "dead-alive-only"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_json_roundtrip() {
+        let mut g = Grid::new(4, 3);
+        g.set_cellstate(1, 0, CellState::Alive);
+        g.set_cellstate(3, 2, CellState::Alive);
+        let json = serde_json::to_string(&g).unwrap();
+        assert_eq!(
+            json,
+            "{\"horizontal_size\":4,\"vertical_size\":3,\"cells\":[\
+             \"Dead\",\"Alive\",\"Dead\",\"Dead\",\
+             \"Dead\",\"Dead\",\"Dead\",\"Dead\",\
+             \"Dead\",\"Dead\",\"Dead\",\"Alive\"]}"
+        );
+        let back: Grid = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, g);
+    }
+
+    /// Check if the given JSON is rejected as grid.
+    fn rejected(json: &str) -> bool {
+        serde_json::from_str::<Grid>(json).is_err()
+    }
+
+    #[test]
+    fn serde_json_errors() {
+        // too few cells
+        assert!(rejected(
+            "{\"horizontal_size\":2,\"vertical_size\":1,\"cells\":[\"Dead\"]}"
+        ));
+        // too many cells
+        assert!(rejected(
+            "{\"horizontal_size\":1,\"vertical_size\":1,\"cells\":[\"Dead\",\"Dead\"]}"
+        ));
+        // invalid dimensions
+        assert!(rejected(
+            "{\"horizontal_size\":0,\"vertical_size\":1,\"cells\":[]}"
+        ));
+        // cells before dimensions
+        assert!(rejected(
+            "{\"cells\":[\"Dead\"],\"horizontal_size\":1,\"vertical_size\":1}"
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+//! A grid sized at compile time for small automata.
+use crate::{CellState, Direction, Grid, HORIZONTAL_MAX, VERTICAL_MAX};
+
+/// A toroidal grid of `W` x `H` cells. In contrast to `Grid`
+/// the backing arrays are sized exactly to the dimensions,
+/// so e.g. a 16x16 grid only needs a few hundred bytes.
+///
+/// # Remarks
+/// The dimensions are checked at compile time: each has to be
+/// at least one cell and must not exceed the maximal size of a `Grid`.
+#[derive(Copy, Clone, Debug)]
+pub struct StaticGrid<const W: usize, const H: usize> {
+    /// The current cell states, row by row.
+    cells: [[CellState; W]; H],
+    /// Temporary internal buffer to calculate the new state.
+    shadow: [[CellState; W]; H],
+}
+
+impl<const W: usize, const H: usize> StaticGrid<W, H> {
+    /// Fails the build if a dimension is out of range.
+    const SIZE_CHECK: () = assert!(
+        W > 0 && W <= HORIZONTAL_MAX && H > 0 && H <= VERTICAL_MAX,
+        "invalid grid dimensions"
+    );
+
+    /// Create a new grid filled with default (dead) cells.
+    pub fn new() -> StaticGrid<W, H> {
+        let () = Self::SIZE_CHECK;
+        StaticGrid {
+            cells: [[CellState::default(); W]; H],
+            shadow: [[CellState::default(); W]; H],
+        }
+    }
+
+    /// Get the horizontal size of the grid.
+    pub fn get_horizontal_size(&self) -> u8 {
+        W as u8
+    }
+
+    /// Get the vertical size of the grid.
+    pub fn get_vertical_size(&self) -> u8 {
+        H as u8
+    }
+
+    /// Retrieve a cell state.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    pub fn get_cellstate(&self, h: u8, v: u8) -> &CellState {
+        if h as usize >= W {
+            panic!("horizontal coordinate too large")
+        }
+        if v as usize >= H {
+            panic!("vertical coordinate too large")
+        }
+        &self.cells[v as usize][h as usize]
+    }
+
+    /// Set a cell state.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    /// * `state`: the new state of the cell
+    pub fn set_cellstate(&mut self, h: u8, v: u8, state: CellState) {
+        if h as usize >= W {
+            panic!("horizontal coordinate too large")
+        }
+        if v as usize >= H {
+            panic!("vertical coordinate too large")
+        }
+        self.cells[v as usize][h as usize] = state;
+    }
+
+    /// Get coordinates of the neighbouring cell in the given
+    /// direction, wrapping around at all edges.
+    ///
+    /// # Arguments
+    /// * `h`: horizontal coordinate
+    /// * `v`: vertical coordinate
+    /// * `direction`: direction of the neighbour
+    pub fn neighbour(&self, h: u8, v: u8, direction: Direction) -> (u8, u8) {
+        if h as usize >= W {
+            panic!("horizontal coordinate too large")
+        }
+        if v as usize >= H {
+            panic!("vertical coordinate too large")
+        }
+        let (dh, dv) = match direction {
+            Direction::North => (0, H - 1),
+            Direction::NorthEast => (1, H - 1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (W - 1, 1),
+            Direction::West => (W - 1, 0),
+            Direction::NorthWest => (W - 1, H - 1),
+        };
+        (((h as usize + dh) % W) as u8, ((v as usize + dv) % H) as u8)
+    }
+
+    /// Apply a rule to every cell (all cells are updated at once).
+    ///
+    /// # Arguments
+    /// * `rule`: calculates the new state of a cell from the current grid
+    pub fn update(&mut self, rule: fn(u8, u8, &StaticGrid<W, H>) -> CellState) {
+        for v in 0..H as u8 {
+            for h in 0..W as u8 {
+                self.shadow[v as usize][h as usize] = rule(h, v, self);
+            }
+        }
+        core::mem::swap(&mut self.cells, &mut self.shadow);
+    }
+
+    /// Copy the cell states into a (runtime sized) `Grid`,
+    /// e.g. to use the utility functions of a `Grid`.
+    pub fn to_grid(&self) -> Grid {
+        let mut grid = Grid::new(W as u8, H as u8);
+        for v in 0..H as u8 {
+            for h in 0..W as u8 {
+                grid.set_cellstate(h, v, *self.get_cellstate(h, v));
+            }
+        }
+        grid
+    }
+}
+
+impl<const W: usize, const H: usize> Default for StaticGrid<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_grid_new() {
+        let g = StaticGrid::<4, 3>::new();
+        assert_eq!(g.get_horizontal_size(), 4);
+        assert_eq!(g.get_vertical_size(), 3);
+        #[cfg(feature = "dead-alive-only")]
+        for v in 0..3 {
+            for h in 0..4 {
+                assert_eq!(g.get_cellstate(h, v), &CellState::Dead);
+            }
+        }
+    }
+
+    #[test]
+    fn static_grid_size() {
+        // sized by its dimensions instead of the maximal grid
+        let small = core::mem::size_of::<StaticGrid<4, 4>>();
+        assert_eq!(small, 2 * 16 * core::mem::size_of::<CellState>());
+        assert!(small * 100 < core::mem::size_of::<Grid>());
+    }
+
+    #[test]
+    fn static_grid_neighbour() {
+        let g = StaticGrid::<4, 3>::new();
+        assert_eq!(g.neighbour(0, 0, Direction::North), (0, 2));
+        assert_eq!(g.neighbour(0, 0, Direction::NorthWest), (3, 2));
+        assert_eq!(g.neighbour(3, 2, Direction::SouthEast), (0, 0));
+        assert_eq!(g.neighbour(1, 1, Direction::East), (2, 1));
+        assert_eq!(g.neighbour(1, 1, Direction::SouthWest), (0, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn static_grid_get_too_large() {
+        let g = StaticGrid::<4, 4>::new();
+        let _ = g.get_cellstate(0, 4);
+    }
+
+    #[test]
+    #[cfg(feature = "dead-alive-only")]
+    fn static_grid_update() {
+        // a cell becomes alive if its western neighbour is alive
+        fn shift_east(h: u8, v: u8, g: &StaticGrid<4, 4>) -> CellState {
+            let (wh, wv) = g.neighbour(h, v, Direction::West);
+            *g.get_cellstate(wh, wv)
+        }
+
+        let mut g = StaticGrid::<4, 4>::new();
+        g.set_cellstate(3, 1, CellState::Alive);
+        g.update(shift_east);
+        assert_eq!(g.get_cellstate(0, 1), &CellState::Alive);
+        assert_eq!(g.get_cellstate(3, 1), &CellState::Dead);
+
+        let grid = g.to_grid();
+        assert_eq!(grid.get_horizontal_size(), 4);
+        assert_eq!(grid.get_cellstate(0, 1), &CellState::Alive);
+        assert_eq!(grid.population(), 1);
+    }
+}
@@ -0,0 +1,58 @@
+//! Rules shared by the tests of several modules.
+use crate::{CellState, Grid};
+
+/// Conway's Game of Life (B3/S23), wrapping around all edges.
+///
+/// # Arguments
+/// * `h`: horizontal coordinate
+/// * `v`: vertical coordinate
+/// * `g`: the current grid
+pub(crate) fn conway(h: u8, v: u8, g: &Grid) -> CellState {
+    let neighbours = [
+        g.get_north_coordinate(h, v),
+        g.get_northeast_coordinate(h, v),
+        g.get_east_coordinate(h, v),
+        g.get_southeast_coordinate(h, v),
+        g.get_south_coordinate(h, v),
+        g.get_southwest_coordinate(h, v),
+        g.get_west_coordinate(h, v),
+        g.get_northwest_coordinate(h, v),
+    ];
+    let mut alive = 0;
+    for n in neighbours.iter() {
+        if g.get_cellstate_hv(*n) == &CellState::Alive {
+            alive += 1;
+        }
+    }
+    match (g.get_cellstate(h, v), alive) {
+        (CellState::Alive, 2) | (_, 3) => CellState::Alive,
+        _ => CellState::Dead,
+    }
+}
+
+/// Wolfram rule 30 on the row of each cell, wrapping around
+/// the edges (as in the rule 30 example).
+///
+/// # Arguments
+/// * `h`: horizontal coordinate
+/// * `v`: vertical coordinate
+/// * `g`: the current grid
+pub(crate) fn rule30(h: u8, v: u8, g: &Grid) -> CellState {
+    let left = g.get_west_coordinate(h, v);
+    let right = g.get_east_coordinate(h, v);
+    let state = (
+        g.get_cellstate_hv(left),
+        g.get_cellstate(h, v),
+        g.get_cellstate_hv(right),
+    );
+    match state {
+        (CellState::Alive, CellState::Alive, CellState::Alive) => CellState::Dead,
+        (CellState::Alive, CellState::Alive, CellState::Dead) => CellState::Dead,
+        (CellState::Alive, CellState::Dead, CellState::Alive) => CellState::Dead,
+        (CellState::Alive, CellState::Dead, CellState::Dead) => CellState::Alive,
+        (CellState::Dead, CellState::Alive, CellState::Alive) => CellState::Alive,
+        (CellState::Dead, CellState::Alive, CellState::Dead) => CellState::Alive,
+        (CellState::Dead, CellState::Dead, CellState::Alive) => CellState::Alive,
+        (CellState::Dead, CellState::Dead, CellState::Dead) => CellState::Dead,
+    }
+}